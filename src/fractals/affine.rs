@@ -0,0 +1,75 @@
+use num_complex::Complex;
+
+/// The affine map `(x, y) -> (a·x + b·y + e, c·x + d·y + f)`, applied to the point `x + yi`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Affine {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl Affine {
+    pub fn apply(&self, z: Complex<f64>) -> Complex<f64> {
+        Complex::new(
+            self.a * z.re + self.b * z.im + self.e,
+            self.c * z.re + self.d * z.im + self.f,
+        )
+    }
+}
+
+impl Default for Affine {
+    /// The identity.
+    fn default() -> Affine {
+        Affine {
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+            d: 1.0,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity() {
+        let z = Complex::new(0.3, -1.7);
+        assert_eq!(z, Affine::default().apply(z));
+    }
+
+    #[test]
+    fn test_apply() {
+        let affine = Affine {
+            a: 1.0,
+            b: 2.0,
+            c: 3.0,
+            d: 4.0,
+            e: 5.0,
+            f: 6.0,
+        };
+        assert_eq!(
+            Complex::new(1.0 + 2.0 * 10.0 + 5.0, 3.0 + 4.0 * 10.0 + 6.0),
+            affine.apply(Complex::new(1.0, 10.0))
+        );
+    }
+
+    #[test]
+    fn test_rotation() {
+        let quarter_turn = Affine {
+            a: 0.0,
+            b: -1.0,
+            c: 1.0,
+            d: 0.0,
+            ..Affine::default()
+        };
+        let z = Complex::new(2.0, 1.0);
+        assert_eq!(z * Complex::i(), quarter_turn.apply(z));
+    }
+}
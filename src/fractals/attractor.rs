@@ -0,0 +1,207 @@
+use super::color_scheme::Gradient;
+use super::density::{chunk_rng, Density, ToneMap};
+use super::image::Image;
+use super::job::Renderer;
+use ::image::RgbImage;
+use num_complex::Complex;
+use rand::rngs::StdRng;
+use rand::Rng;
+
+#[cfg(test)]
+use std::any::Any;
+
+/// Points drawn from the generator that `chunk_rng` gives each chunk.
+const POINTS_PER_CHUNK: u64 = 100_000;
+
+/// Points each chunk throws away while its orbit is still making its way to the attractor.
+const SETTLING_POINTS: u64 = 100;
+
+/// Maps of the plane with a strange attractor, taking `x + yi` to the next point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Map {
+    /// `(sin(a·y) + c·cos(a·x), sin(b·x) + d·cos(b·y))`
+    Clifford,
+    /// Peter de Jong's `(sin(a·y) - cos(b·x), sin(c·x) - cos(d·y))`
+    DeJong,
+    /// `(1 - a·x² + y, b·x)`; c and d are not used.
+    Henon,
+}
+
+impl Map {
+    pub fn apply(&self, z: Complex<f64>, a: f64, b: f64, c: f64, d: f64) -> Complex<f64> {
+        let (x, y) = (z.re, z.im);
+        match self {
+            Map::Clifford => Complex::new(
+                (a * y).sin() + c * (a * x).cos(),
+                (b * x).sin() + d * (b * y).cos(),
+            ),
+            Map::DeJong => {
+                Complex::new((a * y).sin() - (b * x).cos(), (c * x).sin() - (d * y).cos())
+            }
+            Map::Henon => Complex::new(1.0 - a * x * x + y, b * x),
+        }
+    }
+}
+
+/// A strange attractor drawn by following one orbit of `map` for `points` steps and counting
+/// the visits to each pixel.  The counts are tone mapped and looked up in `gradient`.
+#[derive(Debug)]
+pub struct StrangeAttractor {
+    pub map: Map,
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub points: u64,
+    pub seed: u64,
+    pub gradient: Gradient,
+    pub tone_map: ToneMap,
+}
+
+impl StrangeAttractor {
+    /// Every chunk follows its own orbit from a random point near the origin.  An orbit that runs
+    /// off to infinity starts over, and the points it spends settling down again are not counted.
+    pub fn density(&self, image: &Image, threads: usize) -> Density<1> {
+        let chunks = self.points.div_ceil(POINTS_PER_CHUNK);
+        Density::accumulate(&image.size, threads, chunks, |chunk, hits| {
+            let mut rng = chunk_rng(self.seed, chunk);
+            let points = POINTS_PER_CHUNK.min(self.points - chunk * POINTS_PER_CHUNK);
+            let random_point =
+                |rng: &mut StdRng| Complex::new(rng.gen_range(-0.1..0.1), rng.gen_range(-0.1..0.1));
+            let mut z = random_point(&mut rng);
+            let mut settling = SETTLING_POINTS;
+            for _ in 0..SETTLING_POINTS + points {
+                z = self.map.apply(z, self.a, self.b, self.c, self.d);
+                if !z.is_finite() {
+                    z = random_point(&mut rng);
+                    settling = SETTLING_POINTS;
+                } else if settling > 0 {
+                    settling -= 1;
+                } else {
+                    if let Some((col, row)) = image.pixel_at(z) {
+                        hits.add(col, row, [1]);
+                    }
+                }
+            }
+        })
+    }
+}
+
+impl Renderer for StrangeAttractor {
+    fn render(&self, image: &Image, threads: usize) -> RgbImage {
+        self.density(image, threads)
+            .to_gradient_image(&self.gradient, self.tone_map)
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::color_scheme::Color;
+    use crate::fractals::size::Size;
+
+    fn image(upper_left: Complex<f64>, lower_right: Complex<f64>) -> Image {
+        Image {
+            input_filename: "".to_string(),
+            output_filename: "".to_string(),
+            size: Size {
+                width: 60,
+                height: 60,
+            },
+            upper_left,
+            lower_right,
+        }
+    }
+
+    fn attractor(map: Map, a: f64, b: f64, c: f64, d: f64) -> StrangeAttractor {
+        StrangeAttractor {
+            map,
+            a,
+            b,
+            c,
+            d,
+            points: 200_000,
+            seed: 0,
+            gradient: Gradient::new(vec![Color::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0)]),
+            tone_map: ToneMap::Log,
+        }
+    }
+
+    fn total(density: &Density<1>) -> u64 {
+        let mut total = 0;
+        for row in 0..60 {
+            for col in 0..60 {
+                total += density.get(col, row)[0];
+            }
+        }
+        total
+    }
+
+    #[test]
+    fn test_maps() {
+        let z = Complex::new(0.5, -1.0);
+        let (a, b, c, d) = (1.5, -0.5, 2.0, 0.25);
+        assert_eq!(
+            Complex::new(
+                (-1.5f64).sin() + 2.0 * 0.75f64.cos(),
+                (-0.25f64).sin() + 0.25 * 0.5f64.cos()
+            ),
+            Map::Clifford.apply(z, a, b, c, d)
+        );
+        assert_eq!(
+            Complex::new(
+                (-1.5f64).sin() - (-0.25f64).cos(),
+                1.0f64.sin() - (-0.25f64).cos()
+            ),
+            Map::DeJong.apply(z, a, b, c, d)
+        );
+        assert_eq!(
+            Complex::new(1.0 - 1.5 * 0.25 - 1.0, -0.25),
+            Map::Henon.apply(z, a, b, c, d)
+        );
+    }
+
+    #[test]
+    fn test_henon_stays_on_its_attractor() {
+        let image = image(Complex::new(-1.5, 0.5), Complex::new(1.5, -0.5));
+        let henon = attractor(Map::Henon, 1.4, 0.3, 0.0, 0.0);
+        let density = henon.density(&image, 2);
+        assert_eq!(200_000, total(&density));
+        // The attractor is a thin set of curves, so most of the box stays empty.
+        let empty = (0..60)
+            .flat_map(|row| (0..60).map(move |col| (col, row)))
+            .filter(|(col, row)| density.get(*col, *row) == [0])
+            .count();
+        assert!(empty > 60 * 60 / 2);
+    }
+
+    #[test]
+    fn test_clifford_is_bounded() {
+        // |x| ≤ 1 + |c| and |y| ≤ 1 + |d|.
+        let image = image(Complex::new(-2.0, 1.75), Complex::new(2.0, -1.75));
+        let clifford = attractor(Map::Clifford, -1.4, 1.6, 1.0, 0.7);
+        assert_eq!(200_000, total(&clifford.density(&image, 2)));
+    }
+
+    #[test]
+    fn test_density_is_reproducible() {
+        let image = image(Complex::new(-2.0, 2.0), Complex::new(2.0, -2.0));
+        let de_jong = attractor(Map::DeJong, 1.4, -2.3, 2.4, -2.1);
+        let density = de_jong.density(&image, 1);
+        for threads in [2, 3] {
+            assert_eq!(density, de_jong.density(&image, threads));
+        }
+    }
+
+    #[test]
+    fn test_escaping_orbits_start_over() {
+        let image = image(Complex::new(-2.0, 2.0), Complex::new(2.0, -2.0));
+        let henon = attractor(Map::Henon, 3.0, 1.0, 0.0, 0.0);
+        assert_eq!(Density::new(&image.size), henon.density(&image, 1));
+    }
+}
@@ -0,0 +1,136 @@
+use num_complex::Complex;
+
+/// The region an orbit must stay inside to count as not yet escaped.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Bailout {
+    /// `|z| < radius`, the usual escape circle.
+    Circle(f64),
+    /// `max(|Re z|, |Im z|) < radius`, the L∞ norm.
+    Square(f64),
+    /// `|Re z| + |Im z| < radius`, the L1 norm.
+    L1(f64),
+    /// `|Re z| < limit`, for families such as `exp(z) + c` that blow up along the real axis.
+    Real(f64),
+    /// `|Im z| < limit`, for families such as `sin(z) + c` that blow up along the imaginary axis.
+    Imaginary(f64),
+    /// Pickover's biomorph rule: escapes the circle as usual, but an orbit that leaves with
+    /// either `|Re z|` or `|Im z|` still under the radius is drawn as inside.
+    Biomorph(f64),
+}
+
+impl Bailout {
+    pub fn contains(&self, z: Complex<f64>) -> bool {
+        match self {
+            Bailout::Circle(radius) | Bailout::Biomorph(radius) => z.norm_sqr() < radius * radius,
+            Bailout::Square(radius) => z.re.abs() < *radius && z.im.abs() < *radius,
+            Bailout::L1(radius) => z.re.abs() + z.im.abs() < *radius,
+            Bailout::Real(limit) => z.re.abs() < *limit,
+            Bailout::Imaginary(limit) => z.im.abs() < *limit,
+        }
+    }
+
+    /// Whether an orbit that has already escaped at z is nevertheless drawn as inside.
+    pub fn captures(&self, z: Complex<f64>) -> bool {
+        match self {
+            Bailout::Biomorph(radius) => z.re.abs() < *radius || z.im.abs() < *radius,
+            _ => false,
+        }
+    }
+
+    /// The size of the region, which smooth coloring treats as the escape radius.
+    pub fn radius(&self) -> f64 {
+        match self {
+            Bailout::Circle(radius)
+            | Bailout::Square(radius)
+            | Bailout::L1(radius)
+            | Bailout::Biomorph(radius) => *radius,
+            Bailout::Real(limit) | Bailout::Imaginary(limit) => *limit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circle() {
+        let bailout = Bailout::Circle(2.0);
+        assert!(bailout.contains(Complex::new(1.0, 1.0)));
+        assert!(bailout.contains(Complex::new(0.0, -1.99)));
+        assert!(!bailout.contains(Complex::new(2.0, 0.0)));
+        assert!(!bailout.contains(Complex::new(1.5, 1.5)));
+    }
+
+    #[test]
+    fn test_square() {
+        let bailout = Bailout::Square(2.0);
+        assert!(bailout.contains(Complex::new(1.9, -1.9)));
+        assert!(!bailout.contains(Complex::new(2.0, 0.0)));
+        assert!(!bailout.contains(Complex::new(0.0, -2.5)));
+    }
+
+    #[test]
+    fn test_l1() {
+        let bailout = Bailout::L1(2.0);
+        assert!(bailout.contains(Complex::new(0.9, -0.9)));
+        assert!(bailout.contains(Complex::new(0.0, 1.9)));
+        assert!(!bailout.contains(Complex::new(1.0, 1.0)));
+        assert!(!bailout.contains(Complex::new(-1.5, 0.6)));
+    }
+
+    #[test]
+    fn test_biomorph() {
+        let bailout = Bailout::Biomorph(10.0);
+        assert!(bailout.contains(Complex::new(7.0, 7.0)));
+        assert!(!bailout.contains(Complex::new(8.0, 8.0)));
+        assert!(bailout.captures(Complex::new(3.0, 200.0)));
+        assert!(bailout.captures(Complex::new(-200.0, 9.0)));
+        assert!(!bailout.captures(Complex::new(11.0, -11.0)));
+    }
+
+    #[test]
+    fn test_only_biomorph_captures() {
+        let z = Complex::new(0.0, 100.0);
+        assert!(!Bailout::Circle(2.0).captures(z));
+        assert!(!Bailout::Square(2.0).captures(z));
+        assert!(!Bailout::L1(2.0).captures(z));
+        assert!(!Bailout::Real(2.0).captures(z));
+        assert!(!Bailout::Imaginary(2.0).captures(z));
+    }
+
+    #[test]
+    fn test_real() {
+        let bailout = Bailout::Real(50.0);
+        assert!(bailout.contains(Complex::new(49.0, 1000.0)));
+        assert!(!bailout.contains(Complex::new(-50.0, 0.0)));
+    }
+
+    #[test]
+    fn test_imaginary() {
+        let bailout = Bailout::Imaginary(50.0);
+        assert!(bailout.contains(Complex::new(1000.0, -49.0)));
+        assert!(!bailout.contains(Complex::new(0.0, 50.0)));
+    }
+
+    #[test]
+    fn test_not_a_number_escapes() {
+        let nan = Complex::new(f64::NAN, f64::NAN);
+        assert!(!Bailout::Circle(2.0).contains(nan));
+        assert!(!Bailout::Square(2.0).contains(nan));
+        assert!(!Bailout::L1(2.0).contains(nan));
+        assert!(!Bailout::Biomorph(2.0).contains(nan));
+        assert!(!Bailout::Real(2.0).contains(nan));
+        assert!(!Bailout::Imaginary(2.0).contains(nan));
+    }
+
+    #[test]
+    fn test_radius() {
+        assert_eq!(2.0, Bailout::Circle(2.0).radius());
+        assert_eq!(2.0, Bailout::Square(2.0).radius());
+        assert_eq!(2.0, Bailout::L1(2.0).radius());
+        assert_eq!(10.0, Bailout::Biomorph(10.0).radius());
+        assert_eq!(50.0, Bailout::Real(50.0).radius());
+        assert_eq!(50.0, Bailout::Imaginary(50.0).radius());
+    }
+}
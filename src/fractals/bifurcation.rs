@@ -0,0 +1,185 @@
+use super::color_scheme::Gradient;
+use super::density::{Density, ToneMap};
+use super::image::Image;
+use super::job::Renderer;
+use ::image::RgbImage;
+use num_complex::Complex;
+use std::f64::consts::PI;
+
+#[cfg(test)]
+use std::any::Any;
+
+/// Maps of the real line with a parameter `r`, taking `x` to the next value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RealMap {
+    /// `r·x·(1 - x)`, which goes through its period doublings for r from 3 to about 3.57.
+    Logistic,
+    /// `r·sin(π·x)`, with the same cascade as the logistic map for r up to 1.
+    Sine,
+    /// `r·min(x, 1 - x)`, chaotic everywhere above r = 1.
+    Tent,
+    /// `x² + r`, the real line of the Mandelbrot set for r from -2 to 1/4.
+    Quadratic,
+}
+
+impl RealMap {
+    pub fn apply(&self, x: f64, r: f64) -> f64 {
+        match self {
+            RealMap::Logistic => r * x * (1.0 - x),
+            RealMap::Sine => r * (PI * x).sin(),
+            RealMap::Tent => r * x.min(1.0 - x),
+            RealMap::Quadratic => x * x + r,
+        }
+    }
+}
+
+/// The bifurcation diagram of `map`: the parameter sweeps the real axis of the image and the
+/// values an orbit keeps visiting once its `transient` has died out are counted along the
+/// imaginary axis.
+///
+/// Every column follows `samples` orbits from `x0`, with parameters spread across the width of
+/// the pixel, and counts `points` values of each.  The counts are tone mapped and looked up in
+/// `gradient`.
+#[derive(Debug)]
+pub struct Bifurcation {
+    pub map: RealMap,
+    pub x0: f64,
+    pub transient: u64,
+    pub points: u64,
+    pub samples: u64,
+    pub gradient: Gradient,
+    pub tone_map: ToneMap,
+}
+
+impl Bifurcation {
+    pub fn density(&self, image: &Image, threads: usize) -> Density<1> {
+        let chunks = image.size.width as u64;
+        Density::accumulate(&image.size, threads, chunks, |col, hits| {
+            for sample in 0..self.samples {
+                let offset = (sample as f64 + 0.5) / self.samples as f64 - 0.5;
+                let r = image.left() + (col as f64 + offset) * image.x_delta();
+                let mut x = self.x0;
+                for iteration in 0..self.transient + self.points {
+                    x = self.map.apply(x, r);
+                    if !x.is_finite() {
+                        break;
+                    }
+                    if iteration >= self.transient {
+                        if let Some((col, row)) = image.pixel_at(Complex::new(r, x)) {
+                            hits.add(col, row, [1]);
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+impl Renderer for Bifurcation {
+    fn render(&self, image: &Image, threads: usize) -> RgbImage {
+        self.density(image, threads)
+            .to_gradient_image(&self.gradient, self.tone_map)
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::color_scheme::Color;
+    use crate::fractals::size::Size;
+
+    /// One column per 0.01 of the parameter and one row per 0.01 of x.
+    fn image(left: f64, right: f64, bottom: f64, top: f64) -> Image {
+        Image {
+            input_filename: "".to_string(),
+            output_filename: "".to_string(),
+            size: Size {
+                width: ((right - left) * 100.0).round() as u32 + 1,
+                height: ((top - bottom) * 100.0).round() as u32 + 1,
+            },
+            upper_left: Complex::new(left, top),
+            lower_right: Complex::new(right, bottom),
+        }
+    }
+
+    fn logistic() -> Bifurcation {
+        Bifurcation {
+            map: RealMap::Logistic,
+            x0: 0.5,
+            transient: 1000,
+            points: 200,
+            samples: 1,
+            gradient: Gradient::new(vec![Color::new(1.0, 1.0, 1.0), Color::new(0.0, 0.0, 0.0)]),
+            tone_map: ToneMap::Log,
+        }
+    }
+
+    /// The rows of `col` that were hit.
+    fn hit_rows(density: &Density<1>, col: u32) -> Vec<u32> {
+        (0..density.height())
+            .filter(|&row| density.get(col, row) != [0])
+            .collect()
+    }
+
+    #[test]
+    fn test_maps() {
+        assert_eq!(0.75, RealMap::Logistic.apply(0.5, 3.0));
+        assert!((RealMap::Sine.apply(0.5, 0.8) - 0.8).abs() < 1e-12);
+        assert_eq!(0.5, RealMap::Tent.apply(0.75, 2.0));
+        assert_eq!(-0.75, RealMap::Quadratic.apply(0.5, -1.0));
+    }
+
+    #[test]
+    fn test_period_doubling() {
+        let image = image(2.8, 3.5, 0.0, 1.0);
+        let density = logistic().density(&image, 2);
+        // A fixed point at 1 - 1/r, ...
+        assert_eq!(vec![36], hit_rows(&density, 0));
+        // ... a 2-cycle, ...
+        assert_eq!(2, hit_rows(&density, 40).len());
+        // ... and a 4-cycle at r = 3.5.
+        assert_eq!(4, hit_rows(&density, 70).len());
+        assert_eq!([200], density.get(0, 36));
+    }
+
+    #[test]
+    fn test_chaos_spreads_out() {
+        let image = image(3.9, 4.0, 0.0, 1.0);
+        let density = logistic().density(&image, 1);
+        assert!(hit_rows(&density, 0).len() > 50);
+    }
+
+    #[test]
+    fn test_escaping_orbits_are_not_counted() {
+        let image = image(0.3, 0.5, -2.0, 2.0);
+        let mut quadratic = logistic();
+        quadratic.map = RealMap::Quadratic;
+        quadratic.x0 = 0.0;
+        let density = quadratic.density(&image, 2);
+        assert_eq!(Density::new(&image.size), density);
+    }
+
+    #[test]
+    fn test_density_is_reproducible() {
+        let image = image(2.5, 4.0, 0.0, 1.0);
+        let mut bifurcation = logistic();
+        bifurcation.samples = 3;
+        let density = bifurcation.density(&image, 1);
+        for threads in [2, 7] {
+            assert_eq!(density, bifurcation.density(&image, threads));
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let image = image(2.5, 4.0, 0.0, 1.0);
+        let buffer = logistic().render(&image, 2);
+        assert_eq!(151, buffer.width());
+        assert_eq!(101, buffer.height());
+    }
+}
@@ -0,0 +1,177 @@
+use super::bailout::Bailout;
+use super::density::{chunk_rng, Density, Hits, ToneMap};
+use super::escape_time::{cycle_degree, escape_time_with_previous, Iteration, Step};
+use super::image::Image;
+use super::job::Renderer;
+use super::plane::Plane;
+use ::image::RgbImage;
+use num_complex::Complex;
+use rand::Rng;
+
+#[cfg(test)]
+use std::any::Any;
+
+/// Samples drawn from the generator that `chunk_rng` gives each chunk.
+const SAMPLES_PER_CHUNK: u64 = 10_000;
+
+/// The Buddhabrot: instead of coloring each pixel by its own orbit, it picks random points in the
+/// square of `sample_radius` around 0, iterates `steps` from each one as if it were a pixel, and
+/// counts how often the orbits that escape pass through every pixel of the view.
+///
+/// Each color channel has its own iteration limit and only counts orbits that escape within it.
+/// The same limit in all three gives the Buddhabrot in gray; the Nebulabrot uses something like
+/// 5000, 500 and 50 for red, green and blue.
+#[derive(Debug)]
+pub struct Buddhabrot {
+    pub max_iterations: [i64; 3],
+    pub plane: Plane,
+    pub bailout: Bailout,
+    pub steps: Vec<Box<dyn Step>>,
+    pub samples: u64,
+    pub sample_radius: f64,
+    pub seed: u64,
+    pub tone_map: ToneMap,
+}
+
+impl Buddhabrot {
+    pub fn density(&self, image: &Image, threads: usize) -> Density {
+        let chunks = self.samples.div_ceil(SAMPLES_PER_CHUNK);
+        Density::accumulate(&image.size, threads, chunks, |chunk, hits| {
+            let mut rng = chunk_rng(self.seed, chunk);
+            let mut orbit = Vec::new();
+            let samples = SAMPLES_PER_CHUNK.min(self.samples - chunk * SAMPLES_PER_CHUNK);
+            for _ in 0..samples {
+                let sample = Complex::new(
+                    rng.gen_range(-self.sample_radius..self.sample_radius),
+                    rng.gen_range(-self.sample_radius..self.sample_radius),
+                );
+                self.trace(sample, &mut orbit, image, hits);
+            }
+        })
+    }
+
+    /// Adds the orbit of `sample` to the channels whose limit it escapes within.
+    fn trace(
+        &self,
+        sample: Complex<f64>,
+        orbit: &mut Vec<Complex<f64>>,
+        image: &Image,
+        hits: &mut Hits<3>,
+    ) {
+        let (z0, c) = self.plane.start(sample, Complex::new(0.0, 0.0));
+        let max_iterations = self.max_iterations.iter().copied().max().unwrap_or(0);
+        let degree = cycle_degree(&self.steps);
+        let mut steps = self.steps.iter().cycle();
+        orbit.clear();
+        let iteration =
+            escape_time_with_previous(z0, &self.bailout, max_iterations, degree, |z, _| {
+                let z = steps.next().map_or(z, |step| step.apply(z, c));
+                orbit.push(z);
+                z
+            });
+
+        if let Iteration::Outside { iterations, .. } = iteration {
+            let channels = self.max_iterations.map(|limit| (iterations < limit) as u64);
+            // The last point is already outside the bailout.
+            if let Some((_, inside)) = orbit.split_last() {
+                for z in inside {
+                    if let Some((col, row)) = image.pixel_at(*z) {
+                        hits.add(col, row, channels);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Renderer for Buddhabrot {
+    fn render(&self, image: &Image, threads: usize) -> RgbImage {
+        self.density(image, threads).to_image(self.tone_map)
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::mandelbrot::MandelbrotStep;
+    use crate::fractals::power::Power;
+    use crate::fractals::size::Size;
+
+    fn image() -> Image {
+        Image {
+            input_filename: "".to_string(),
+            output_filename: "".to_string(),
+            size: Size {
+                width: 40,
+                height: 30,
+            },
+            upper_left: Complex::new(-2.0, 1.5),
+            lower_right: Complex::new(1.0, -1.5),
+        }
+    }
+
+    fn nebulabrot(samples: u64, seed: u64) -> Buddhabrot {
+        Buddhabrot {
+            max_iterations: [200, 50, 10],
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(2.0),
+            steps: vec![Box::new(MandelbrotStep {
+                power: Power::Integer(2),
+            })],
+            samples,
+            sample_radius: 2.0,
+            seed,
+            tone_map: ToneMap::Sqrt,
+        }
+    }
+
+    #[test]
+    fn test_density_is_reproducible() {
+        let image = image();
+        let density = nebulabrot(25_000, 7).density(&image, 1);
+        assert_ne!(Density::new(&image.size), density);
+        for threads in [1, 2, 5] {
+            assert_eq!(density, nebulabrot(25_000, 7).density(&image, threads));
+        }
+        assert_ne!(density, nebulabrot(25_000, 8).density(&image, 1));
+        assert_ne!(
+            nebulabrot(25_000, 0).density(&image, 1),
+            nebulabrot(25_000, 1).density(&image, 1)
+        );
+    }
+
+    #[test]
+    fn test_higher_limits_count_more() {
+        let image = image();
+        let density = nebulabrot(20_000, 1).density(&image, 2);
+        for row in 0..30 {
+            for col in 0..40 {
+                let [red, green, blue] = density.get(col, row);
+                assert!(red >= green && green >= blue);
+            }
+        }
+        let [red, green, blue] = density.max();
+        assert!(red > green && green > blue);
+    }
+
+    #[test]
+    fn test_orbits_that_never_escape_are_not_counted() {
+        let image = image();
+        let mut buddhabrot = nebulabrot(5_000, 1);
+        buddhabrot.sample_radius = 0.2;
+        assert_eq!(Density::new(&image.size), buddhabrot.density(&image, 2));
+    }
+
+    #[test]
+    fn test_render() {
+        let image = image();
+        let buffer = nebulabrot(5_000, 1).render(&image, 2);
+        assert_eq!(40, buffer.width());
+        assert_eq!(30, buffer.height());
+    }
+}
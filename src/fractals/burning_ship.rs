@@ -1,23 +1,70 @@
-use super::escape_time::{escape_time, EscapeTime, Iteration};
+use super::bailout::Bailout;
+use super::escape_time::{escape_time, EscapeTime, Iteration, Step};
+use super::plane::Plane;
+use super::power::Power;
 use num_complex::Complex;
 
 #[cfg(test)]
 use std::any::Any;
 
+/// Where the Burning Ship family takes absolute values.  With `z = x + iy`, for the usual
+/// squaring:
+///
+/// - `BurningShip`: `x² - y² - 2|xy|i`
+/// - `Celtic`: `|x² - y²| + 2xyi`
+/// - `Buffalo`: `|x² - y²| - 2|xy|i`
+/// - `Perpendicular`: `x² - y² - 2x|y|i`
+/// - `Heart`: `x² - y² + 2|x|yi`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Variant {
+    BurningShip,
+    Celtic,
+    Buffalo,
+    Perpendicular,
+    Heart,
+}
+
+impl Variant {
+    /// Applied to z before raising it to the power.
+    fn before(&self, z: Complex<f64>) -> Complex<f64> {
+        match self {
+            Variant::BurningShip | Variant::Buffalo => Complex::new(z.re.abs(), -z.im.abs()),
+            Variant::Celtic => z,
+            Variant::Perpendicular => Complex::new(z.re, -z.im.abs()),
+            Variant::Heart => Complex::new(z.re.abs(), z.im),
+        }
+    }
+
+    /// Applied to the power before adding c.
+    fn after(&self, z: Complex<f64>) -> Complex<f64> {
+        match self {
+            Variant::Celtic | Variant::Buffalo => Complex::new(z.re.abs(), z.im),
+            _ => z,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct BurningShip {
     pub max_iterations: i64,
-    pub escape_length: f64,
+    pub variant: Variant,
+    pub plane: Plane,
+    pub bailout: Bailout,
+    pub power: Power,
 }
 
 impl EscapeTime for BurningShip {
-    fn iterate(&self, c: &Complex<f64>) -> Iteration {
+    fn iterate(&self, pixel: &Complex<f64>) -> Iteration {
+        let (z0, c) = self.plane.start(*pixel, Complex::new(0.0, 0.0));
         escape_time(
-            Complex::new(0.0, 0.0),
-            *c,
-            self.escape_length,
+            z0,
+            c,
+            &self.bailout,
             self.max_iterations,
-            |z| Complex::new(z.re.abs(), -z.im.abs()),
+            &[BurningShipStep {
+                variant: self.variant,
+                power: self.power,
+            }],
         )
     }
 
@@ -27,6 +74,23 @@ impl EscapeTime for BurningShip {
     }
 }
 
+/// One iteration of `z^n + c` with the variant's absolute values.
+#[derive(Debug)]
+pub struct BurningShipStep {
+    pub variant: Variant,
+    pub power: Power,
+}
+
+impl Step for BurningShipStep {
+    fn apply(&self, z: Complex<f64>, c: Complex<f64>) -> Complex<f64> {
+        self.variant.after(self.power.apply(self.variant.before(z))) + c
+    }
+
+    fn degree(&self) -> f64 {
+        self.power.degree()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -35,7 +99,10 @@ mod tests {
     fn test_iterate_inside() {
         let bs = BurningShip {
             max_iterations: 512,
-            escape_length: 2.0,
+            variant: Variant::BurningShip,
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(2.0),
+            power: Power::default(),
         };
 
         assert_eq!(
@@ -51,13 +118,19 @@ mod tests {
     fn test_iterate_outside() {
         let bs = BurningShip {
             max_iterations: 512,
-            escape_length: 2.0,
+            variant: Variant::BurningShip,
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(2.0),
+            power: Power::default(),
         };
 
         assert_eq!(
             Iteration::Outside {
                 iterations: 1,
-                max_iterations: 512
+                max_iterations: 512,
+                final_z: Complex::new(2.0, 2.0),
+                escape_length: 2.0,
+                degree: 2.0,
             },
             bs.iterate(&Complex::new(2.0, 2.0))
         );
@@ -74,7 +147,10 @@ mod tests {
         // - With incorrect formula (positive imaginary): escapes quickly
         let bs = BurningShip {
             max_iterations: 50,
-            escape_length: 2.0,
+            variant: Variant::BurningShip,
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(2.0),
+            power: Power::default(),
         };
 
         // With correct formula (negative imaginary), this point should stay inside
@@ -89,6 +165,144 @@ mod tests {
                  the wrong sign (positive imaginary), which would flip the fractal upside-down.",
                 iterations
             ),
+            Iteration::Converged { .. } => panic!("The burning ship never converges"),
+        }
+    }
+
+    #[test]
+    fn test_dynamical_plane() {
+        // Starting the Julia form at z0 = 0 retraces the parameter-plane orbit of the same c.
+        let c = Complex::new(-1.76, -0.03);
+        let parameter = BurningShip {
+            max_iterations: 256,
+            variant: Variant::BurningShip,
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(2.0),
+            power: Power::default(),
+        };
+        let dynamical = BurningShip {
+            plane: Plane::Dynamical(c),
+            ..parameter
+        };
+
+        assert_eq!(
+            parameter.iterate(&c),
+            dynamical.iterate(&Complex::new(0.0, 0.0))
+        );
+        assert!(matches!(
+            dynamical.iterate(&Complex::new(2.0, 2.0)),
+            Iteration::Outside { iterations: 0, .. }
+        ));
+    }
+
+    fn variant(variant: Variant) -> BurningShip {
+        BurningShip {
+            max_iterations: 512,
+            variant,
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(2.0),
+            power: Power::default(),
         }
     }
+
+    #[test]
+    fn test_variants_agree_at_power_two() {
+        let (x, y) = (0.3, -0.7);
+        let z = Complex::new(x, y);
+        let c = Complex::new(0.0, 0.0);
+        let step = |v: Variant| v.after(Power::default().apply(v.before(z))) + c;
+
+        assert_eq!(
+            Complex::new(x * x - y * y, -2.0 * (x * y).abs()),
+            step(Variant::BurningShip)
+        );
+        assert_eq!(
+            Complex::new((x * x - y * y).abs(), 2.0 * x * y),
+            step(Variant::Celtic)
+        );
+        assert_eq!(
+            Complex::new((x * x - y * y).abs(), -2.0 * (x * y).abs()),
+            step(Variant::Buffalo)
+        );
+        assert_eq!(
+            Complex::new(x * x - y * y, -2.0 * x * y.abs()),
+            step(Variant::Perpendicular)
+        );
+        assert_eq!(
+            Complex::new(x * x - y * y, 2.0 * x.abs() * y),
+            step(Variant::Heart)
+        );
+    }
+
+    #[test]
+    fn test_orientation_celtic() {
+        // The Celtic set is symmetric top to bottom but not left to right.
+        let celtic = variant(Variant::Celtic);
+        assert!(matches!(
+            celtic.iterate(&Complex::new(-0.2, -0.4)),
+            Iteration::Inside { .. }
+        ));
+        assert!(matches!(
+            celtic.iterate(&Complex::new(-0.2, 0.4)),
+            Iteration::Inside { .. }
+        ));
+        assert!(matches!(
+            celtic.iterate(&Complex::new(0.2, -0.4)),
+            Iteration::Outside { iterations: 6, .. }
+        ));
+    }
+
+    #[test]
+    fn test_orientation_buffalo() {
+        // Like the Burning Ship, the Buffalo's antenna reaches left just above the real axis.
+        let buffalo = variant(Variant::Buffalo);
+        assert!(matches!(
+            buffalo.iterate(&Complex::new(-1.5, 0.1)),
+            Iteration::Inside { .. }
+        ));
+        assert!(matches!(
+            buffalo.iterate(&Complex::new(-1.5, -0.1)),
+            Iteration::Outside { iterations: 5, .. }
+        ));
+    }
+
+    #[test]
+    fn test_orientation_perpendicular() {
+        // Symmetric neither way.
+        let perpendicular = variant(Variant::Perpendicular);
+        assert!(matches!(
+            perpendicular.iterate(&Complex::new(-0.9, -0.2)),
+            Iteration::Inside { .. }
+        ));
+        assert!(matches!(
+            perpendicular.iterate(&Complex::new(-0.9, 0.2)),
+            Iteration::Outside { iterations: 6, .. }
+        ));
+        assert!(matches!(
+            perpendicular.iterate(&Complex::new(0.2, 0.3)),
+            Iteration::Inside { .. }
+        ));
+        assert!(matches!(
+            perpendicular.iterate(&Complex::new(-0.2, 0.3)),
+            Iteration::Outside { iterations: 8, .. }
+        ));
+    }
+
+    #[test]
+    fn test_orientation_heart() {
+        // The Heart is symmetric top to bottom but not left to right.
+        let heart = variant(Variant::Heart);
+        assert!(matches!(
+            heart.iterate(&Complex::new(0.2, -0.4)),
+            Iteration::Inside { .. }
+        ));
+        assert!(matches!(
+            heart.iterate(&Complex::new(0.2, 0.4)),
+            Iteration::Inside { .. }
+        ));
+        assert!(matches!(
+            heart.iterate(&Complex::new(-0.2, -0.4)),
+            Iteration::Outside { iterations: 6, .. }
+        ));
+    }
 }
@@ -0,0 +1,117 @@
+use super::bailout::Bailout;
+use super::escape_time::{escape_time, EscapeTime, Iteration, Step};
+use num_complex::Complex;
+use std::f64::consts::PI;
+
+#[cfg(test)]
+use std::any::Any;
+
+/// The Collatz map extended to the complex plane, iterated from the pixel.  On the integers it
+/// is `n/2` for even `n` and `3n + 1` for odd `n`.
+///
+/// `cos(πz)` overflows once `|Im z|` passes a few hundred, so an orbit can jump from well inside
+/// the bailout straight to infinity or NaN; the bailout treats both as escaped.
+#[derive(Debug)]
+pub struct Collatz {
+    pub max_iterations: i64,
+    pub bailout: Bailout,
+}
+
+impl EscapeTime for Collatz {
+    fn iterate(&self, z0: &Complex<f64>) -> Iteration {
+        escape_time(
+            *z0,
+            Complex::new(0.0, 0.0),
+            &self.bailout,
+            self.max_iterations,
+            &[CollatzStep],
+        )
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// One iteration of `(2 + 7z - (2 + 5z)cos(πz)) / 4`; there is no c.
+#[derive(Debug)]
+pub struct CollatzStep;
+
+impl Step for CollatzStep {
+    fn apply(&self, z: Complex<f64>, _: Complex<f64>) -> Complex<f64> {
+        (2.0 + 7.0 * z - (2.0 + 5.0 * z) * (PI * z).cos()) / 4.0
+    }
+
+    /// cos grows faster than any power.
+    fn degree(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collatz(z: Complex<f64>) -> Complex<f64> {
+        CollatzStep.apply(z, Complex::new(0.0, 0.0))
+    }
+
+    fn collatz_fractal() -> Collatz {
+        Collatz {
+            max_iterations: 200,
+            bailout: Bailout::Circle(1e10),
+        }
+    }
+
+    #[test]
+    fn test_integers() {
+        for (n, next) in &[(6.0, 3.0), (3.0, 10.0), (1.0, 4.0), (2.0, 1.0)] {
+            let z = collatz(Complex::new(*n, 0.0));
+            assert!((z - next).norm() < 1e-9, "{} -> {}", n, z);
+        }
+    }
+
+    #[test]
+    fn test_iterate_inside() {
+        assert!(matches!(
+            collatz_fractal().iterate(&Complex::new(6.0, 0.0)),
+            Iteration::Inside { .. }
+        ));
+        assert!(matches!(
+            collatz_fractal().iterate(&Complex::new(-0.5, 0.1)),
+            Iteration::Inside { .. }
+        ));
+    }
+
+    #[test]
+    fn test_iterate_outside() {
+        assert!(matches!(
+            collatz_fractal().iterate(&Complex::new(0.0, 1.0)),
+            Iteration::Outside { iterations: 2, .. }
+        ));
+        assert!(matches!(
+            collatz_fractal().iterate(&Complex::new(0.3, 0.2)),
+            Iteration::Outside { iterations: 4, .. }
+        ));
+    }
+
+    #[test]
+    fn test_overflow_escapes() {
+        let z = collatz(Complex::new(0.3, 300.0));
+        assert!(!z.is_finite());
+        assert!(matches!(
+            collatz_fractal().iterate(&Complex::new(0.3, 300.0)),
+            Iteration::Outside { iterations: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_not_a_number_escapes() {
+        let nan = Complex::new(f64::NAN, 0.0);
+        assert!(matches!(
+            collatz_fractal().iterate(&nan),
+            Iteration::Outside { iterations: 0, .. }
+        ));
+    }
+}
@@ -0,0 +1,99 @@
+use super::{Color, ColorScheme};
+use crate::fractals::escape_time::Iteration;
+
+/// Spreads the hues of consecutive roots around the color wheel without knowing how many there
+/// are.
+const GOLDEN_RATIO_CONJUGATE: f32 = 0.618_034;
+
+/// Gives each root of a `Converged` orbit its own hue, darker the longer the orbit took to get
+/// there.
+#[derive(Debug, PartialEq)]
+pub struct Basins {}
+
+impl ColorScheme for Basins {
+    fn color(&self, iter: Iteration) -> Color {
+        match iter {
+            Iteration::Inside { .. } => Color::new(0.0, 0.0, 0.0),
+            Iteration::Outside {
+                iterations,
+                max_iterations,
+                ..
+            } => {
+                let intensity = 1.0 - speed(iterations, max_iterations);
+                Color::new(intensity, intensity, intensity)
+            }
+            Iteration::Converged {
+                root_index,
+                iterations,
+                max_iterations,
+            } => Color::from_hsv(
+                (root_index as f32 * GOLDEN_RATIO_CONJUGATE).fract(),
+                0.75,
+                speed(iterations, max_iterations),
+            ),
+        }
+    }
+}
+
+/// 1.0 for an immediate result, falling towards 0.0 at `max_iterations`.
+fn speed(iterations: i64, max_iterations: i64) -> f32 {
+    1.0 - (iterations as f32 / max_iterations.max(1) as f32)
+        .sqrt()
+        .min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_complex::Complex;
+
+    fn converged(root_index: usize, iterations: i64) -> Iteration {
+        Iteration::Converged {
+            root_index,
+            iterations,
+            max_iterations: 64,
+        }
+    }
+
+    #[test]
+    fn test_inside_black() {
+        assert_eq!(
+            Color::new(0.0, 0.0, 0.0),
+            Basins {}.color(Iteration::Inside {
+                iterations: 64,
+                max_iterations: 64
+            })
+        );
+    }
+
+    #[test]
+    fn test_roots_get_different_hues() {
+        let cs = Basins {};
+        assert_eq!(Color::new(1.0, 0.25, 0.25), cs.color(converged(0, 0)));
+        assert_ne!(cs.color(converged(0, 0)), cs.color(converged(1, 0)));
+        assert_ne!(cs.color(converged(1, 0)), cs.color(converged(2, 0)));
+        assert_ne!(cs.color(converged(0, 0)), cs.color(converged(2, 0)));
+    }
+
+    #[test]
+    fn test_slow_convergence_is_darker() {
+        let cs = Basins {};
+        assert_eq!(Color::new(0.5, 0.125, 0.125), cs.color(converged(0, 16)));
+        assert_eq!(Color::new(0.0, 0.0, 0.0), cs.color(converged(0, 64)));
+    }
+
+    #[test]
+    fn test_outside_gray() {
+        let cs = Basins {};
+        assert_eq!(
+            Color::new(0.5, 0.5, 0.5),
+            cs.color(Iteration::Outside {
+                iterations: 16,
+                max_iterations: 64,
+                final_z: Complex::new(2.0, 0.0),
+                escape_length: 2.0,
+                degree: 2.0,
+            })
+        );
+    }
+}
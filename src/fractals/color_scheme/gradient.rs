@@ -0,0 +1,290 @@
+use super::{Color, ColorScheme};
+use crate::fractals::escape_time::Iteration;
+
+/// Colors pinned at positions from 0.0 to 1.0 and blended linearly in between.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Gradient {
+    stops: Vec<(f64, Color)>,
+}
+
+impl Gradient {
+    /// Spreads `colors` evenly from 0.0 to 1.0.
+    pub fn new(colors: Vec<Color>) -> Gradient {
+        assert!(!colors.is_empty(), "a gradient needs at least one color");
+        let last = (colors.len() - 1).max(1) as f64;
+        let stops = colors
+            .into_iter()
+            .enumerate()
+            .map(|(i, color)| (i as f64 / last, color))
+            .collect();
+        Gradient { stops }
+    }
+
+    /// Stops must be non-empty and sorted by position.
+    pub fn from_stops(stops: Vec<(f64, Color)>) -> Gradient {
+        assert!(!stops.is_empty(), "a gradient needs at least one stop");
+        assert!(
+            stops.windows(2).all(|pair| pair[0].0 <= pair[1].0),
+            "gradient stops must be sorted by position"
+        );
+        Gradient { stops }
+    }
+
+    pub fn color_at(&self, t: f64) -> Color {
+        let (first_position, first_color) = &self.stops[0];
+        let (last_position, last_color) = &self.stops[self.stops.len() - 1];
+        if t.is_nan() || t <= *first_position {
+            return first_color.clone();
+        }
+        if t >= *last_position {
+            return last_color.clone();
+        }
+        let upper = self
+            .stops
+            .iter()
+            .position(|(position, _)| *position > t)
+            .unwrap();
+        let (from_position, from) = &self.stops[upper - 1];
+        let (to_position, to) = &self.stops[upper];
+        mix(
+            from,
+            to,
+            ((t - from_position) / (to_position - from_position)) as f32,
+        )
+    }
+}
+
+impl Default for Gradient {
+    fn default() -> Self {
+        Gradient::new(vec![
+            Color::new(0.0, 0.03, 0.39),
+            Color::new(0.93, 1.0, 1.0),
+            Color::new(1.0, 0.67, 0.0),
+            Color::new(0.0, 0.01, 0.0),
+        ])
+    }
+}
+
+/// How positions outside of 0.0 to 1.0 are folded back onto a gradient.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Spread {
+    Clamp,
+    Repeat,
+    Mirror,
+}
+
+impl Spread {
+    pub fn apply(&self, t: f64) -> f64 {
+        match self {
+            Spread::Clamp => t.clamp(0.0, 1.0),
+            Spread::Repeat => t.rem_euclid(1.0),
+            Spread::Mirror => {
+                let t = t.rem_euclid(2.0);
+                if t > 1.0 {
+                    2.0 - t
+                } else {
+                    t
+                }
+            }
+        }
+    }
+}
+
+/// Walks a user-defined gradient by iteration count: the position is `offset + iterations *
+/// density`, folded back onto the gradient by `spread`.  Without a density, the gradient spans
+/// the whole iteration range once.
+#[derive(Debug)]
+pub struct GradientScheme {
+    pub gradient: Gradient,
+    pub spread: Spread,
+    pub offset: f64,
+    pub density: Option<f64>,
+}
+
+impl ColorScheme for GradientScheme {
+    fn color(&self, iter: Iteration) -> Color {
+        match iter {
+            Iteration::Inside { .. } => Color::new(0.0, 0.0, 0.0),
+            Iteration::Outside {
+                iterations,
+                max_iterations,
+                ..
+            }
+            | Iteration::Converged {
+                iterations,
+                max_iterations,
+                ..
+            } => {
+                let density = self
+                    .density
+                    .unwrap_or_else(|| 1.0 / max_iterations.max(1) as f64);
+                let t = self.offset + iterations as f64 * density;
+                self.gradient.color_at(self.spread.apply(t))
+            }
+        }
+    }
+}
+
+fn mix(from: &Color, to: &Color, fraction: f32) -> Color {
+    Color::new(
+        from.red + (to.red - from.red) * fraction,
+        from.green + (to.green - from.green) * fraction,
+        from.blue + (to.blue - from.blue) * fraction,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_eq_float::*;
+    use num_complex::Complex;
+
+    fn black_to_white() -> Gradient {
+        Gradient::new(vec![Color::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0)])
+    }
+
+    #[test]
+    fn test_color_at_ends() {
+        let gradient = black_to_white();
+        assert_eq!(Color::new(0.0, 0.0, 0.0), gradient.color_at(0.0));
+        assert_eq!(Color::new(1.0, 1.0, 1.0), gradient.color_at(1.0));
+    }
+
+    #[test]
+    fn test_color_at_interpolates() {
+        let gradient = black_to_white();
+        assert_eq!(Color::new(0.25, 0.25, 0.25), gradient.color_at(0.25));
+
+        let gradient = Gradient::new(vec![
+            Color::new(1.0, 0.0, 0.0),
+            Color::new(0.0, 1.0, 0.0),
+            Color::new(0.0, 0.0, 1.0),
+        ]);
+        assert_eq!(Color::new(0.0, 1.0, 0.0), gradient.color_at(0.5));
+        assert_eq!(Color::new(0.0, 0.5, 0.5), gradient.color_at(0.75));
+    }
+
+    #[test]
+    fn test_color_at_clamps() {
+        let gradient = black_to_white();
+        assert_eq!(Color::new(0.0, 0.0, 0.0), gradient.color_at(-3.0));
+        assert_eq!(Color::new(1.0, 1.0, 1.0), gradient.color_at(7.5));
+        assert_eq!(Color::new(0.0, 0.0, 0.0), gradient.color_at(f64::NAN));
+    }
+
+    #[test]
+    fn test_single_color() {
+        let gradient = Gradient::new(vec![Color::new(0.2, 0.4, 0.6)]);
+        assert_eq!(Color::new(0.2, 0.4, 0.6), gradient.color_at(0.7));
+    }
+
+    #[test]
+    fn test_from_stops() {
+        let gradient = Gradient::from_stops(vec![
+            (0.2, Color::new(1.0, 0.0, 0.0)),
+            (0.6, Color::new(0.0, 0.0, 1.0)),
+            (0.6, Color::new(0.0, 1.0, 0.0)),
+            (1.0, Color::new(1.0, 1.0, 1.0)),
+        ]);
+        assert_eq!(Color::new(1.0, 0.0, 0.0), gradient.color_at(0.1));
+        assert_eq!(Color::new(0.5, 0.0, 0.5), gradient.color_at(0.4));
+        assert_eq!(Color::new(0.0, 1.0, 0.0), gradient.color_at(0.6));
+        assert_eq!(Color::new(0.5, 1.0, 0.5), gradient.color_at(0.8));
+    }
+
+    #[test]
+    fn test_spread_clamp() {
+        assert_eq_float!(0.0, Spread::Clamp.apply(-0.5));
+        assert_eq_float!(0.25, Spread::Clamp.apply(0.25));
+        assert_eq_float!(1.0, Spread::Clamp.apply(3.25));
+    }
+
+    #[test]
+    fn test_spread_repeat() {
+        assert_eq_float!(0.5, Spread::Repeat.apply(-0.5));
+        assert_eq_float!(0.25, Spread::Repeat.apply(0.25));
+        assert_eq_float!(0.25, Spread::Repeat.apply(3.25));
+    }
+
+    #[test]
+    fn test_spread_mirror() {
+        assert_eq_float!(0.5, Spread::Mirror.apply(-0.5));
+        assert_eq_float!(0.25, Spread::Mirror.apply(0.25));
+        assert_eq_float!(0.75, Spread::Mirror.apply(1.25));
+        assert_eq_float!(0.75, Spread::Mirror.apply(3.25));
+    }
+
+    mod gradient_scheme {
+        use super::*;
+
+        fn outside(iterations: i64) -> Iteration {
+            Iteration::Outside {
+                iterations,
+                max_iterations: 100,
+                final_z: Complex::new(2.0, 0.0),
+                escape_length: 2.0,
+                degree: 2.0,
+            }
+        }
+
+        fn scheme(spread: Spread, offset: f64, density: Option<f64>) -> GradientScheme {
+            GradientScheme {
+                gradient: black_to_white(),
+                spread,
+                offset,
+                density,
+            }
+        }
+
+        #[test]
+        fn test_inside_black() {
+            let cs = scheme(Spread::Repeat, 0.5, None);
+            assert_eq!(
+                Color::new(0.0, 0.0, 0.0),
+                cs.color(Iteration::Inside {
+                    iterations: 100,
+                    max_iterations: 100
+                })
+            );
+        }
+
+        #[test]
+        fn test_spans_iteration_range_by_default() {
+            let cs = scheme(Spread::Clamp, 0.0, None);
+            assert_eq!(Color::new(0.25, 0.25, 0.25), cs.color(outside(25)));
+            assert_eq!(Color::new(1.0, 1.0, 1.0), cs.color(outside(100)));
+        }
+
+        #[test]
+        fn test_offset_and_density() {
+            let cs = scheme(Spread::Clamp, 0.5, Some(0.125));
+            assert_eq!(Color::new(0.75, 0.75, 0.75), cs.color(outside(2)));
+            assert_eq!(Color::new(1.0, 1.0, 1.0), cs.color(outside(5)));
+        }
+
+        #[test]
+        fn test_converged_matches_outside() {
+            let cs = scheme(Spread::Repeat, 0.25, Some(0.1));
+            let converged = Iteration::Converged {
+                root_index: 0,
+                iterations: 7,
+                max_iterations: 100,
+            };
+            assert_eq!(cs.color(outside(7)), cs.color(converged));
+        }
+
+        #[test]
+        fn test_repeat_cycles() {
+            let cs = scheme(Spread::Repeat, 0.0, Some(0.25));
+            assert_eq!(cs.color(outside(1)), cs.color(outside(5)));
+            assert_eq!(Color::new(0.0, 0.0, 0.0), cs.color(outside(8)));
+        }
+
+        #[test]
+        fn test_mirror_bounces() {
+            let cs = scheme(Spread::Mirror, 0.0, Some(0.25));
+            assert_eq!(cs.color(outside(3)), cs.color(outside(5)));
+            assert_eq!(Color::new(1.0, 1.0, 1.0), cs.color(outside(4)));
+        }
+    }
+}
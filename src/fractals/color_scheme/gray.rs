@@ -8,7 +8,7 @@ impl ColorScheme for BlackOnWhite {
     fn color(&self, iter: Iteration) -> Color {
         match iter {
             Iteration::Inside { .. } => Color::new(0.0, 0.0, 0.0),
-            Iteration::Outside { .. } => Color::new(1.0, 1.0, 1.0),
+            Iteration::Outside { .. } | Iteration::Converged { .. } => Color::new(1.0, 1.0, 1.0),
         }
     }
 }
@@ -20,7 +20,9 @@ impl ColorScheme for Gray {
     fn color(&self, iter: Iteration) -> Color {
         match iter {
             Iteration::Inside { .. } => Color::new(0.0, 0.0, 0.0),
-            Iteration::Outside { iterations, .. } => gray_scale(iterations),
+            Iteration::Outside { iterations, .. } | Iteration::Converged { iterations, .. } => {
+                gray_scale(iterations)
+            }
         }
     }
 }
@@ -32,7 +34,7 @@ impl ColorScheme for WhiteOnBlack {
     fn color(&self, iter: Iteration) -> Color {
         match iter {
             Iteration::Inside { .. } => Color::new(1.0, 1.0, 1.0),
-            Iteration::Outside { .. } => Color::new(0.0, 0.0, 0.0),
+            Iteration::Outside { .. } | Iteration::Converged { .. } => Color::new(0.0, 0.0, 0.0),
         }
     }
 }
@@ -45,6 +47,7 @@ fn gray_scale(iterations: i64) -> Color {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use num_complex::Complex;
 
     fn inside(iterations: i64) -> Iteration {
         Iteration::Inside {
@@ -57,6 +60,17 @@ mod tests {
         Iteration::Outside {
             iterations: iterations,
             max_iterations: 512,
+            final_z: Complex::new(0.0, 0.0),
+            escape_length: 2.0,
+            degree: 2.0,
+        }
+    }
+
+    fn converged(iterations: i64) -> Iteration {
+        Iteration::Converged {
+            root_index: 1,
+            iterations,
+            max_iterations: 512,
         }
     }
 
@@ -73,6 +87,13 @@ mod tests {
             prop_assert_eq!(Color::new(1.0, 1.0, 1.0), color);
           }
 
+          #[test]
+          fn test_converged_always_white(iterations in 0i64..1024)  {
+            let cs = BlackOnWhite {};
+            let color = cs.color(converged(iterations));
+            prop_assert_eq!(Color::new(1.0, 1.0, 1.0), color);
+          }
+
           #[test]
           fn test_inside_always_black(iterations in 0i64..1024)  {
             let cs = BlackOnWhite {};
@@ -106,6 +127,14 @@ mod tests {
             let color = cs.color(outside(64));
             assert_eq!(Color::new(0.35355338, 0.35355338, 0.35355338), color);
         }
+
+        #[test]
+        fn test_converged_scaled_gray() {
+            let cs = Gray {};
+
+            let color = cs.color(converged(128));
+            assert_eq!(Color::new(0.5, 0.5, 0.5), color);
+        }
     }
 
     mod white_on_black {
@@ -127,6 +156,13 @@ mod tests {
                 let color = cs.color(outside(iterations));
                 prop_assert_eq!(Color::new(0.0, 0.0, 0.0), color);
             }
+
+            #[test]
+            fn test_converged_always_black(iterations in 0i64..1024)  {
+                let cs = WhiteOnBlack {};
+                let color = cs.color(converged(iterations));
+                prop_assert_eq!(Color::new(0.0, 0.0, 0.0), color);
+            }
         }
     }
 }
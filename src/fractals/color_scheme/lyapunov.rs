@@ -0,0 +1,80 @@
+use super::{Color, Gradient};
+use crate::fractals::lyapunov::Exponent;
+
+/// Colors stable (negative) exponents from `stable` and chaotic (positive) ones from `chaotic`.
+/// Each gradient starts at an exponent of 0 and reaches its end as the exponent goes to ±∞.
+#[derive(Debug, PartialEq)]
+pub struct LyapunovColors {
+    pub stable: Gradient,
+    pub chaotic: Gradient,
+}
+
+impl LyapunovColors {
+    pub fn color(&self, Exponent(lambda): Exponent) -> Color {
+        if lambda.is_nan() {
+            Color::new(0.0, 0.0, 0.0)
+        } else if lambda <= 0.0 {
+            self.stable.color_at(1.0 - lambda.exp())
+        } else {
+            self.chaotic.color_at(1.0 - (-lambda).exp())
+        }
+    }
+}
+
+impl Default for LyapunovColors {
+    /// The usual gold for stability and blue for chaos, both dark near 0.
+    fn default() -> Self {
+        LyapunovColors {
+            stable: Gradient::new(vec![Color::new(0.0, 0.0, 0.0), Color::new(1.0, 0.82, 0.0)]),
+            chaotic: Gradient::new(vec![Color::new(0.0, 0.0, 0.0), Color::new(0.0, 0.25, 1.0)]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn red_and_blue() -> LyapunovColors {
+        LyapunovColors {
+            stable: Gradient::new(vec![Color::new(0.0, 0.0, 0.0), Color::new(1.0, 0.0, 0.0)]),
+            chaotic: Gradient::new(vec![Color::new(0.0, 0.0, 0.0), Color::new(0.0, 0.0, 1.0)]),
+        }
+    }
+
+    #[test]
+    fn test_zero_is_the_start_of_the_stable_gradient() {
+        assert_eq!(
+            Color::new(0.0, 0.0, 0.0),
+            red_and_blue().color(Exponent(0.0))
+        );
+    }
+
+    #[test]
+    fn test_stable() {
+        assert_eq!(
+            Color::new(0.5, 0.0, 0.0),
+            red_and_blue().color(Exponent(-(2.0f64.ln())))
+        );
+        assert_eq!(
+            Color::new(1.0, 0.0, 0.0),
+            red_and_blue().color(Exponent(f64::NEG_INFINITY))
+        );
+    }
+
+    #[test]
+    fn test_chaotic() {
+        assert_eq!(
+            Color::new(0.0, 0.0, 0.5),
+            red_and_blue().color(Exponent(2.0f64.ln()))
+        );
+    }
+
+    #[test]
+    fn test_no_exponent_is_black() {
+        assert_eq!(
+            Color::new(0.0, 0.0, 0.0),
+            LyapunovColors::default().color(Exponent(f64::NAN))
+        );
+    }
+}
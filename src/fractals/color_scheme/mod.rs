@@ -1,16 +1,24 @@
+mod basins;
+mod gradient;
 mod gray;
+mod lyapunov;
 mod random;
+mod smooth;
 mod warp_pov;
 
+pub use basins::Basins;
+pub use gradient::{Gradient, GradientScheme, Spread};
 pub use gray::{BlackOnWhite, Gray, WhiteOnBlack};
+pub use lyapunov::LyapunovColors;
 pub use random::Random;
+pub use smooth::Smooth;
 pub use warp_pov::{Blue, Green, Red};
 
 use ::image::Rgb;
 
 use super::escape_time::Iteration;
 
-pub trait ColorScheme: std::fmt::Debug {
+pub trait ColorScheme: std::fmt::Debug + Send + Sync {
     fn color(&self, iter: Iteration) -> Color;
 }
 
@@ -26,6 +34,23 @@ impl Color {
         Color { red, green, blue }
     }
 
+    /// `hue` runs from 0.0 to 1.0 around the color wheel, starting at red.
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Color {
+        let sector = hue.rem_euclid(1.0) * 6.0;
+        let fraction = sector - sector.floor();
+        let p = value * (1.0 - saturation);
+        let q = value * (1.0 - saturation * fraction);
+        let t = value * (1.0 - saturation * (1.0 - fraction));
+        match sector as u32 {
+            0 => Color::new(value, t, p),
+            1 => Color::new(q, value, p),
+            2 => Color::new(p, value, t),
+            3 => Color::new(p, q, value),
+            4 => Color::new(t, p, value),
+            _ => Color::new(value, p, q),
+        }
+    }
+
     pub fn as_rgb(&self) -> Rgb<u8> {
         let Color { red, green, blue } = self;
         Rgb([
@@ -62,6 +87,29 @@ mod tests {
         assert_ne!(color1, color2);
     }
 
+    #[test]
+    fn test_from_hsv_primaries() {
+        assert_eq!(Color::new(1.0, 0.0, 0.0), Color::from_hsv(0.0, 1.0, 1.0));
+        assert_eq!(
+            Color::new(0.0, 1.0, 0.0),
+            Color::from_hsv(1.0 / 3.0, 1.0, 1.0)
+        );
+        assert_eq!(
+            Color::new(0.0, 0.0, 1.0),
+            Color::from_hsv(2.0 / 3.0, 1.0, 1.0)
+        );
+        assert_eq!(Color::new(1.0, 0.0, 0.0), Color::from_hsv(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_from_hsv_saturation_and_value() {
+        assert_eq!(Color::new(0.5, 0.5, 0.5), Color::from_hsv(0.3, 0.0, 0.5));
+        assert_eq!(
+            Color::new(1.0, 0.75, 0.5),
+            Color::from_hsv(1.0 / 12.0, 0.5, 1.0)
+        );
+    }
+
     #[test]
     fn test_as_rgb_black() {
         let color = Color::new(0.0, 0.0, 0.0);
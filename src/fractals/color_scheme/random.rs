@@ -25,7 +25,7 @@ impl ColorScheme for Random {
     fn color(&self, iter: Iteration) -> Color {
         match iter {
             Iteration::Inside { .. } => Color::new(0.0, 0.0, 0.0),
-            Iteration::Outside { iterations, .. } => {
+            Iteration::Outside { iterations, .. } | Iteration::Converged { iterations, .. } => {
                 let index = (iterations as usize) % self.colors.len();
                 self.colors[index].clone()
             }
@@ -36,6 +36,7 @@ impl ColorScheme for Random {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use num_complex::Complex;
 
     fn inside(iterations: i64) -> Iteration {
         Iteration::Inside {
@@ -48,6 +49,9 @@ mod tests {
         Iteration::Outside {
             iterations,
             max_iterations: 512,
+            final_z: Complex::new(0.0, 0.0),
+            escape_length: 2.0,
+            degree: 2.0,
         }
     }
 
@@ -75,6 +79,18 @@ mod tests {
         assert_eq!(random.color(inside(512)), Color::new(0.0, 0.0, 0.0));
     }
 
+    #[test]
+    fn test_converged_matches_outside() {
+        let random = Random::new();
+
+        let converged = Iteration::Converged {
+            root_index: 2,
+            iterations: 37,
+            max_iterations: 512,
+        };
+        assert_eq!(random.color(outside(37)), random.color(converged));
+    }
+
     #[test]
     fn test_random_color_wraps_around() {
         let random = Random::new();
@@ -0,0 +1,202 @@
+use super::{Color, ColorScheme, Gradient};
+use crate::fractals::escape_time::Iteration;
+use num_complex::Complex;
+
+/// Colors escaping points by their renormalized iteration count, which removes the bands that
+/// integer counts produce.
+#[derive(Debug)]
+pub struct Smooth {
+    pub gradient: Gradient,
+}
+
+impl ColorScheme for Smooth {
+    fn color(&self, iter: Iteration) -> Color {
+        match iter {
+            Iteration::Inside { .. } => Color::new(0.0, 0.0, 0.0),
+            Iteration::Outside {
+                iterations,
+                max_iterations,
+                final_z,
+                escape_length,
+                degree,
+            } => {
+                let count = renormalized_count(iterations, final_z, escape_length, degree);
+                self.gradient
+                    .color_at((count / max_iterations as f64).sqrt())
+            }
+            Iteration::Converged {
+                iterations,
+                max_iterations,
+                ..
+            } => self
+                .gradient
+                .color_at((iterations as f64 / max_iterations as f64).sqrt()),
+        }
+    }
+}
+
+/// `n - log(log|z| / log(bailout)) / log(degree)`, falling back to `n` when the logs are
+/// meaningless or the formula doesn't grow faster than z.
+fn renormalized_count(
+    iterations: i64,
+    final_z: Complex<f64>,
+    escape_length: f64,
+    degree: f64,
+) -> f64 {
+    let count = iterations as f64 - (final_z.norm().ln() / escape_length.ln()).ln() / degree.ln();
+    if count.is_finite() && degree > 1.0 {
+        count.max(0.0)
+    } else {
+        iterations as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::bailout::Bailout;
+    use crate::fractals::escape_time::EscapeTime;
+    use crate::fractals::mandelbrot::Mandelbrot;
+    use crate::fractals::plane::Plane;
+    use crate::fractals::power::Power;
+    use assert_eq_float::*;
+
+    fn black_to_white() -> Smooth {
+        Smooth {
+            gradient: Gradient::new(vec![Color::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0)]),
+        }
+    }
+
+    fn outside(iterations: i64, final_z: Complex<f64>) -> Iteration {
+        Iteration::Outside {
+            iterations,
+            max_iterations: 400,
+            final_z,
+            escape_length: 2.0,
+            degree: 2.0,
+        }
+    }
+
+    #[test]
+    fn test_renormalized_count_at_bailout() {
+        assert_eq_float!(10.0, renormalized_count(10, Complex::new(2.0, 0.0), 2.0, 2.0));
+    }
+
+    #[test]
+    fn test_renormalized_count_past_bailout() {
+        assert_eq_float!(9.0, renormalized_count(10, Complex::new(0.0, 4.0), 2.0, 2.0));
+        assert_eq_float!(8.0, renormalized_count(10, Complex::new(16.0, 0.0), 2.0, 2.0));
+        assert_eq_float!(
+            9.5,
+            renormalized_count(10, Complex::new(2.0f64.powf(2.0f64.sqrt()), 0.0), 2.0, 2.0)
+        );
+    }
+
+    #[test]
+    fn test_renormalized_count_never_negative() {
+        assert_eq_float!(0.0, renormalized_count(0, Complex::new(100.0, 0.0), 2.0, 2.0));
+    }
+
+    #[test]
+    fn test_renormalized_count_degenerate_bailout() {
+        assert_eq_float!(7.0, renormalized_count(7, Complex::new(3.0, 0.0), 1.0, 2.0));
+    }
+
+    #[test]
+    fn test_renormalized_count_uses_degree() {
+        assert_eq_float!(9.0, renormalized_count(10, Complex::new(8.0, 0.0), 2.0, 3.0));
+        assert_eq_float!(8.0, renormalized_count(10, Complex::new(512.0, 0.0), 2.0, 3.0));
+    }
+
+    #[test]
+    fn test_renormalized_count_without_growth() {
+        assert_eq_float!(6.0, renormalized_count(6, Complex::new(5.0, 0.0), 2.0, 1.0));
+        assert_eq_float!(
+            6.0,
+            renormalized_count(6, Complex::new(5.0, 0.0), 2.0, f64::INFINITY)
+        );
+    }
+
+    #[test]
+    fn test_multibrot_count_is_continuous() {
+        let multibrot = Mandelbrot {
+            max_iterations: 400,
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(1000.0),
+            power: Power::Integer(3),
+        };
+        let count = |c: f64| match multibrot.iterate(&Complex::new(c, 0.0)) {
+            Iteration::Outside {
+                iterations,
+                final_z,
+                escape_length,
+                degree,
+                ..
+            } => (
+                iterations,
+                renormalized_count(iterations, final_z, escape_length, degree),
+            ),
+            iteration => panic!("{:?} did not escape", iteration),
+        };
+        // Walking towards the set, the integer count goes up by one at each band boundary while
+        // the smooth count barely moves.
+        let mut boundaries = 0;
+        let (mut previous_iterations, mut previous_count) = count(1.0);
+        for step in 1..=1000 {
+            let (iterations, count) = count(1.0 - step as f64 * 0.0005);
+            if iterations != previous_iterations {
+                boundaries += 1;
+                assert!((count - previous_count).abs() < 0.02);
+            }
+            (previous_iterations, previous_count) = (iterations, count);
+        }
+        assert!(boundaries >= 3);
+    }
+
+    #[test]
+    fn test_inside_black() {
+        let cs = black_to_white();
+        assert_eq!(
+            Color::new(0.0, 0.0, 0.0),
+            cs.color(Iteration::Inside {
+                iterations: 400,
+                max_iterations: 400
+            })
+        );
+    }
+
+    #[test]
+    fn test_outside_uses_gradient() {
+        let cs = black_to_white();
+        assert_eq!(
+            Color::new(0.1, 0.1, 0.1),
+            cs.color(outside(4, Complex::new(2.0, 0.0)))
+        );
+        assert_eq!(
+            Color::new(0.05, 0.05, 0.05),
+            cs.color(outside(2, Complex::new(4.0, 0.0)))
+        );
+    }
+
+    #[test]
+    fn test_converged_uses_integer_count() {
+        let cs = black_to_white();
+        assert_eq!(
+            Color::new(0.1, 0.1, 0.1),
+            cs.color(Iteration::Converged {
+                root_index: 0,
+                iterations: 4,
+                max_iterations: 400
+            })
+        );
+    }
+
+    #[test]
+    fn test_outside_is_continuous_across_bands() {
+        let cs = black_to_white();
+        assert_eq!(
+            cs.color(outside(5, Complex::new(4.0, 0.0))),
+            cs.color(outside(4, Complex::new(2.0, 0.0)))
+        );
+    }
+}
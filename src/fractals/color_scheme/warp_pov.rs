@@ -39,6 +39,12 @@ fn intensities(iter: Iteration) -> (f32, f32) {
         Iteration::Outside {
             iterations,
             max_iterations,
+            ..
+        }
+        | Iteration::Converged {
+            iterations,
+            max_iterations,
+            ..
         } => outside_intensity(iterations, max_iterations),
     }
 }
@@ -59,6 +65,7 @@ fn scale(i: i64, max_iterations: i64) -> f32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use num_complex::Complex;
 
     #[test]
     fn test_blue_is_primary() {
@@ -67,7 +74,10 @@ mod tests {
             Color::new(0.6875, 0.6875, 1.0),
             cs.color(Iteration::Outside {
                 iterations: 432,
-                max_iterations: 512
+                max_iterations: 512,
+                final_z: Complex::new(0.0, 0.0),
+                escape_length: 2.0,
+                degree: 2.0,
             })
         );
     }
@@ -79,7 +89,10 @@ mod tests {
             Color::new(0.6875, 1.0, 0.6875),
             cs.color(Iteration::Outside {
                 iterations: 432,
-                max_iterations: 512
+                max_iterations: 512,
+                final_z: Complex::new(0.0, 0.0),
+                escape_length: 2.0,
+                degree: 2.0,
             })
         );
     }
@@ -90,6 +103,22 @@ mod tests {
         assert_eq!(
             Color::new(1.0, 0.6875, 0.6875),
             cs.color(Iteration::Outside {
+                iterations: 432,
+                max_iterations: 512,
+                final_z: Complex::new(0.0, 0.0),
+                escape_length: 2.0,
+                degree: 2.0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_converged_like_outside() {
+        let cs = Red {};
+        assert_eq!(
+            Color::new(1.0, 0.6875, 0.6875),
+            cs.color(Iteration::Converged {
+                root_index: 0,
                 iterations: 432,
                 max_iterations: 512
             })
@@ -110,35 +139,50 @@ mod tests {
             (0.0, 0.0),
             intensities(Iteration::Outside {
                 iterations: 1,
-                max_iterations: 512
+                max_iterations: 512,
+                final_z: Complex::new(0.0, 0.0),
+                escape_length: 2.0,
+                degree: 2.0,
             })
         );
         assert_eq!(
             (0.9921875, 0.0),
             intensities(Iteration::Outside {
                 iterations: 255,
-                max_iterations: 512
+                max_iterations: 512,
+                final_z: Complex::new(0.0, 0.0),
+                escape_length: 2.0,
+                degree: 2.0,
             })
         );
         assert_eq!(
             (1.0, 0.6875),
             intensities(Iteration::Outside {
                 iterations: 432,
-                max_iterations: 512
+                max_iterations: 512,
+                final_z: Complex::new(0.0, 0.0),
+                escape_length: 2.0,
+                degree: 2.0,
             })
         );
         assert_eq!(
             (1.0, 0.0),
             intensities(Iteration::Outside {
                 iterations: 256,
-                max_iterations: 512
+                max_iterations: 512,
+                final_z: Complex::new(0.0, 0.0),
+                escape_length: 2.0,
+                degree: 2.0,
             })
         );
         assert_eq!(
             (1.0, 1.0),
             intensities(Iteration::Outside {
                 iterations: 512,
-                max_iterations: 512
+                max_iterations: 512,
+                final_z: Complex::new(0.0, 0.0),
+                escape_length: 2.0,
+                degree: 2.0,
             })
         );
     }
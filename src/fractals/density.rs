@@ -0,0 +1,307 @@
+use super::color_scheme::{Color, Gradient};
+use super::size::Size;
+use ::image::{ImageBuffer, RgbImage};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::sync::Mutex;
+use std::thread;
+
+/// How many hits a worker collects before taking the lock on the shared density.
+const HITS_PER_BATCH: usize = 1 << 16;
+
+/// Hit counts for each pixel, for renderers that scatter points over the image instead of
+/// coloring each pixel once.  Usually there is one count per color channel, but a renderer may
+/// keep `CHANNELS` counts of whatever it needs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Density<const CHANNELS: usize = 3> {
+    width: u32,
+    height: u32,
+    counts: Vec<[u64; CHANNELS]>,
+}
+
+impl<const CHANNELS: usize> Density<CHANNELS> {
+    pub fn new(size: &Size) -> Self {
+        Density {
+            width: size.width,
+            height: size.height,
+            counts: vec![[0; CHANNELS]; size.width as usize * size.height as usize],
+        }
+    }
+
+    /// Calls `fun` for every chunk of work in `0..chunks`, spreading the chunks round-robin across
+    /// at most `threads` workers.  The workers add their hits to one shared density in batches,
+    /// and the counts are sums, so the result doesn't depend on the number of threads.
+    pub fn accumulate(
+        size: &Size,
+        threads: usize,
+        chunks: u64,
+        fun: impl Fn(u64, &mut Hits<CHANNELS>) + Sync,
+    ) -> Self {
+        let workers = (threads.max(1) as u64).min(chunks);
+        let density = Mutex::new(Density::new(size));
+        let (fun, shared) = (&fun, &density);
+        thread::scope(|scope| {
+            for worker in 0..workers {
+                scope.spawn(move || {
+                    let mut hits = Hits::new(shared);
+                    let mut chunk = worker;
+                    while chunk < chunks {
+                        fun(chunk, &mut hits);
+                        chunk += workers;
+                    }
+                    hits.flush();
+                });
+            }
+        });
+        density.into_inner().unwrap()
+    }
+
+    pub fn get(&self, col: u32, row: u32) -> [u64; CHANNELS] {
+        self.counts[self.index(col, row)]
+    }
+
+    /// Adds `hits` to the counts of a pixel.
+    pub fn add(&mut self, col: u32, row: u32, hits: [u64; CHANNELS]) {
+        let index = self.index(col, row);
+        for (count, hit) in self.counts[index].iter_mut().zip(hits) {
+            *count += hit;
+        }
+    }
+
+    /// The largest count in each channel.
+    pub fn max(&self) -> [u64; CHANNELS] {
+        self.counts.iter().fold([0; CHANNELS], |mut max, counts| {
+            for (max, count) in max.iter_mut().zip(counts) {
+                *max = (*max).max(*count);
+            }
+            max
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn index(&self, col: u32, row: u32) -> usize {
+        row as usize * self.width as usize + col as usize
+    }
+}
+
+/// The random generator for `chunk` of a render seeded with `seed`.  The seed and the chunk fill
+/// separate halves of the key, so nearby seeds don't share the streams of their chunks the way
+/// `seed + chunk` would.
+pub fn chunk_rng(seed: u64, chunk: u64) -> StdRng {
+    let mut key = [0; 32];
+    key[..8].copy_from_slice(&seed.to_le_bytes());
+    key[16..24].copy_from_slice(&chunk.to_le_bytes());
+    StdRng::from_seed(key)
+}
+
+/// Hits collected by one worker of `Density::accumulate`, added to the shared density whenever
+/// `HITS_PER_BATCH` have piled up.  This keeps the memory of each worker independent of the
+/// image size.
+pub struct Hits<'a, const CHANNELS: usize> {
+    density: &'a Mutex<Density<CHANNELS>>,
+    batch: Vec<(u32, u32, [u64; CHANNELS])>,
+}
+
+impl<'a, const CHANNELS: usize> Hits<'a, CHANNELS> {
+    fn new(density: &'a Mutex<Density<CHANNELS>>) -> Self {
+        Hits {
+            density,
+            batch: Vec::with_capacity(HITS_PER_BATCH),
+        }
+    }
+
+    /// Adds `hits` to the counts of a pixel.
+    pub fn add(&mut self, col: u32, row: u32, hits: [u64; CHANNELS]) {
+        self.batch.push((col, row, hits));
+        if self.batch.len() >= HITS_PER_BATCH {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        let mut density = self.density.lock().unwrap();
+        for (col, row, hits) in self.batch.drain(..) {
+            density.add(col, row, hits);
+        }
+    }
+}
+
+impl Density<3> {
+    /// Each channel is scaled against its own largest count.
+    pub fn to_image(&self, tone_map: ToneMap) -> RgbImage {
+        let max = self.max();
+        ImageBuffer::from_fn(self.width, self.height, |col, row| {
+            let counts = self.get(col, row);
+            Color::new(
+                tone_map.apply(counts[0], max[0]) as f32,
+                tone_map.apply(counts[1], max[1]) as f32,
+                tone_map.apply(counts[2], max[2]) as f32,
+            )
+            .as_rgb()
+        })
+    }
+}
+
+impl Density<1> {
+    /// Looks up the tone-mapped count in `gradient`, so empty pixels get its first color.
+    pub fn to_gradient_image(&self, gradient: &Gradient, tone_map: ToneMap) -> RgbImage {
+        let [max] = self.max();
+        ImageBuffer::from_fn(self.width, self.height, |col, row| {
+            let [count] = self.get(col, row);
+            gradient.color_at(tone_map.apply(count, max)).as_rgb()
+        })
+    }
+}
+
+/// How counts are squeezed into a brightness between 0.0 and 1.0.  Densities typically span
+/// several orders of magnitude, so a linear map leaves all but the brightest pixels black.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ToneMap {
+    Linear,
+    Sqrt,
+    Log,
+}
+
+impl ToneMap {
+    pub fn apply(&self, count: u64, max: u64) -> f64 {
+        if max == 0 {
+            return 0.0;
+        }
+        let (count, max) = (count as f64, max as f64);
+        match self {
+            ToneMap::Linear => count / max,
+            ToneMap::Sqrt => (count / max).sqrt(),
+            ToneMap::Log => count.ln_1p() / max.ln_1p(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::image::Rgb;
+    use assert_eq_float::*;
+    use rand::Rng;
+    use std::collections::HashSet;
+
+    fn size() -> Size {
+        Size {
+            width: 4,
+            height: 3,
+        }
+    }
+
+    #[test]
+    fn test_add_and_get() {
+        let mut density = Density::new(&size());
+        density.add(3, 2, [1, 0, 2]);
+        density.add(3, 2, [1, 1, 0]);
+        assert_eq!([2, 1, 2], density.get(3, 2));
+        assert_eq!([0, 0, 0], density.get(2, 2));
+        assert_eq!([2, 1, 2], density.max());
+    }
+
+    #[test]
+    fn test_accumulate_is_identical_across_thread_counts() {
+        let fill = |chunk: u64, hits: &mut Hits<3>| {
+            hits.add(
+                (chunk % 4) as u32,
+                (chunk % 3) as u32,
+                [chunk, 1, chunk % 2],
+            );
+        };
+        let single = Density::accumulate(&size(), 1, 50, fill);
+        assert_ne!(Density::new(&size()), single);
+        for threads in [2, 3, 8, 100] {
+            assert_eq!(single, Density::accumulate(&size(), threads, 50, fill));
+        }
+    }
+
+    #[test]
+    fn test_accumulate_uses_at_most_one_thread_per_chunk() {
+        let workers = Mutex::new(HashSet::new());
+        let density = Density::accumulate(&size(), 8, 3, |chunk, hits: &mut Hits<1>| {
+            workers.lock().unwrap().insert(thread::current().id());
+            hits.add(chunk as u32, 0, [1]);
+        });
+        assert_eq!(3, workers.into_inner().unwrap().len());
+        assert_eq!([1], density.get(2, 0));
+
+        let empty = Density::accumulate(&size(), 8, 0, |_, _: &mut Hits<1>| unreachable!());
+        assert_eq!(Density::new(&size()), empty);
+    }
+
+    #[test]
+    fn test_accumulate_flushes_full_batches() {
+        let density = Density::accumulate(&size(), 2, 4, |chunk, hits: &mut Hits<1>| {
+            for _ in 0..HITS_PER_BATCH + 10 {
+                hits.add(3, 2, [chunk]);
+            }
+        });
+        assert_eq!([6 * (HITS_PER_BATCH as u64 + 10)], density.get(3, 2));
+    }
+
+    #[test]
+    fn test_chunk_rng_streams_are_independent() {
+        let first = |seed, chunk| chunk_rng(seed, chunk).gen::<u64>();
+        assert_eq!(first(7, 1), first(7, 1));
+        assert_ne!(first(7, 1), first(8, 0));
+        assert_ne!(first(0, 1), first(1, 0));
+    }
+
+    #[test]
+    fn test_chunk_rng_seeds_give_different_densities() {
+        let scatter = |seed| {
+            Density::accumulate(&size(), 2, 4, |chunk, hits: &mut Hits<1>| {
+                let mut rng = chunk_rng(seed, chunk);
+                for _ in 0..100 {
+                    hits.add(rng.gen_range(0..4), rng.gen_range(0..3), [1]);
+                }
+            })
+        };
+        assert_eq!(scatter(0), scatter(0));
+        assert_ne!(scatter(0), scatter(1));
+    }
+
+    #[test]
+    fn test_tone_maps() {
+        assert_eq_float!(0.25, ToneMap::Linear.apply(25, 100));
+        assert_eq_float!(0.5, ToneMap::Sqrt.apply(25, 100));
+        assert_eq_float!(0.5, ToneMap::Log.apply(2, 8));
+        for tone_map in [ToneMap::Linear, ToneMap::Sqrt, ToneMap::Log] {
+            assert_eq_float!(0.0, tone_map.apply(0, 100));
+            assert_eq_float!(1.0, tone_map.apply(100, 100));
+            assert_eq_float!(0.0, tone_map.apply(0, 0));
+        }
+    }
+
+    #[test]
+    fn test_to_gradient_image() {
+        let mut density = Density::new(&size());
+        density.add(0, 0, [4]);
+        density.add(1, 0, [2]);
+        let gradient = Gradient::new(vec![Color::new(0.0, 0.0, 1.0), Color::new(1.0, 0.0, 0.0)]);
+        let image = density.to_gradient_image(&gradient, ToneMap::Linear);
+        assert_eq!(&Rgb([255, 0, 0]), image.get_pixel(0, 0));
+        assert_eq!(&Rgb([127, 0, 127]), image.get_pixel(1, 0));
+        assert_eq!(&Rgb([0, 0, 255]), image.get_pixel(2, 0));
+    }
+
+    #[test]
+    fn test_to_image_scales_channels_separately() {
+        let mut density = Density::new(&size());
+        density.add(0, 0, [10, 1, 0]);
+        density.add(1, 0, [5, 2, 0]);
+        let image = density.to_image(ToneMap::Linear);
+        assert_eq!(&Rgb([255, 127, 0]), image.get_pixel(0, 0));
+        assert_eq!(&Rgb([127, 255, 0]), image.get_pixel(1, 0));
+        assert_eq!(&Rgb([0, 0, 0]), image.get_pixel(3, 2));
+    }
+}
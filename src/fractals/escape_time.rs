@@ -1,17 +1,20 @@
+use super::bailout::Bailout;
 use num_complex::Complex;
 
 #[cfg(test)]
 use std::any::Any;
 
-pub trait EscapeTime: std::fmt::Debug {
+pub trait EscapeTime: std::fmt::Debug + Send + Sync {
     fn iterate(&self, c: &Complex<f64>) -> Iteration;
-    
+
     #[cfg(test)]
     fn as_any(&self) -> &dyn Any;
 }
 
 #[derive(Debug, PartialEq)]
 pub enum Iteration {
+    /// Never escaped, or escaped in a way the bailout rule still counts as inside (see
+    /// `Bailout::Biomorph`).
     Inside {
         iterations: i64,
         max_iterations: i64,
@@ -19,31 +22,132 @@ pub enum Iteration {
     Outside {
         iterations: i64,
         max_iterations: i64,
+        /// The first `z` to land outside the bailout region.
+        final_z: Complex<f64>,
+        escape_length: f64,
+        /// How fast the formula grows once z is large, `|z_next| ≈ |z|^degree`; 2 for z² + c.
+        degree: f64,
+    },
+    /// Settled on one of several attractors, e.g. a root found by Newton's method.
+    Converged {
+        root_index: usize,
+        iterations: i64,
+        max_iterations: i64,
     },
 }
 
-/// Escape-time algorithm: iterates z = transform(z)² + c until |z| > escape_length or max_iterations reached
-pub fn escape_time<F>(
+/// One application of an escape-time formula, taking z to the next iterate.
+pub trait Step: std::fmt::Debug + Send + Sync {
+    fn apply(&self, z: Complex<f64>, c: Complex<f64>) -> Complex<f64>;
+
+    /// The growth of the formula for large z, `|apply(z, c)| ≈ |z|^degree`.
+    fn degree(&self) -> f64;
+}
+
+impl<S: Step + ?Sized> Step for Box<S> {
+    fn apply(&self, z: Complex<f64>, c: Complex<f64>) -> Complex<f64> {
+        (**self).apply(z, c)
+    }
+
+    fn degree(&self) -> f64 {
+        (**self).degree()
+    }
+}
+
+/// The growth per iteration of taking `steps` in turn: the geometric mean of their degrees.
+pub fn cycle_degree<S: Step>(steps: &[S]) -> f64 {
+    let log_sum: f64 = steps.iter().map(|step| step.degree().ln()).sum();
+    (log_sum / steps.len() as f64).exp()
+}
+
+/// Escape-time algorithm: iterates z = step(z, c), taking the steps in turn, until z leaves the
+/// bailout region or max_iterations reached
+pub fn escape_time<S: Step>(
     z0: Complex<f64>,
     c: Complex<f64>,
-    escape_length: f64,
+    bailout: &Bailout,
+    max_iterations: i64,
+    steps: &[S],
+) -> Iteration {
+    let degree = cycle_degree(steps);
+    let mut steps = steps.iter().cycle();
+    escape_time_with_previous(z0, bailout, max_iterations, degree, |z, _| {
+        steps.next().map_or(z, |step| step.apply(z, c))
+    })
+}
+
+/// Escape-time algorithm for formulas that depend on the previous iterate: iterates
+/// z = step(z, z_prev) until z leaves the bailout region or max_iterations reached.  The iterate before
+/// z0 is taken to be 0.  `degree` is the growth of the step for large z, as in `Step::degree`.
+pub fn escape_time_with_previous<F>(
+    z0: Complex<f64>,
+    bailout: &Bailout,
+    max_iterations: i64,
+    degree: f64,
+    mut step: F,
+) -> Iteration
+where
+    F: FnMut(Complex<f64>, Complex<f64>) -> Complex<f64>,
+{
+    let mut z = z0;
+    let mut z_prev = Complex::new(0.0, 0.0);
+    let mut iterations = 0;
+
+    while bailout.contains(z) && iterations < max_iterations {
+        let z_next = step(z, z_prev);
+        z_prev = z;
+        z = z_next;
+        iterations += 1;
+    }
+
+    if iterations >= max_iterations || bailout.captures(z) {
+        Iteration::Inside {
+            iterations,
+            max_iterations,
+        }
+    } else {
+        Iteration::Outside {
+            iterations,
+            max_iterations,
+            final_z: z,
+            escape_length: bailout.radius(),
+            degree,
+        }
+    }
+}
+
+/// Escape-time algorithm that also watches for orbits settling down: iterates z = step(z) until
+/// z leaves the bailout region, `attractor(z, z_prev)` names the attractor that z has reached, or
+/// max_iterations reached.
+pub fn escape_or_converge_time<F, A>(
+    z0: Complex<f64>,
+    bailout: &Bailout,
     max_iterations: i64,
-    transform: F,
+    degree: f64,
+    step: F,
+    attractor: A,
 ) -> Iteration
 where
     F: Fn(Complex<f64>) -> Complex<f64>,
+    A: Fn(Complex<f64>, Complex<f64>) -> Option<usize>,
 {
     let mut z = z0;
     let mut iterations = 0;
-    let escape_threshold = escape_length * escape_length;
 
-    while z.norm_sqr() < escape_threshold && iterations < max_iterations {
-        z = transform(z);
-        z = z * z + c;
+    while bailout.contains(z) && iterations < max_iterations {
+        let z_prev = z;
+        z = step(z);
         iterations += 1;
+        if let Some(root_index) = attractor(z, z_prev) {
+            return Iteration::Converged {
+                root_index,
+                iterations,
+                max_iterations,
+            };
+        }
     }
 
-    if iterations >= max_iterations {
+    if iterations >= max_iterations || bailout.captures(z) {
         Iteration::Inside {
             iterations,
             max_iterations,
@@ -52,6 +156,9 @@ where
         Iteration::Outside {
             iterations,
             max_iterations,
+            final_z: z,
+            escape_length: bailout.radius(),
+            degree,
         }
     }
 }
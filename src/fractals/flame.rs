@@ -0,0 +1,337 @@
+use super::affine::Affine;
+use super::color_scheme::{Color, Gradient};
+use super::density::{chunk_rng, Density, ToneMap};
+use super::image::Image;
+use super::job::Renderer;
+use ::image::{ImageBuffer, Rgb, RgbImage};
+use num_complex::Complex;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::f64::consts::PI;
+
+#[cfg(test)]
+use std::any::Any;
+
+/// Points drawn from the generator that `chunk_rng` gives each chunk.
+const POINTS_PER_CHUNK: u64 = 100_000;
+
+/// Points each chunk throws away while its orbit is still making its way to the attractor.
+const SETTLING_POINTS: u64 = 20;
+
+/// The nonlinear functions of Draves and Reckase's "The Fractal Flame Algorithm", applied to the
+/// point `x + yi` after the affine map.  `r` is `|z|` and `θ` is `atan2(x, y)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Variation {
+    Linear,
+    Sinusoidal,
+    Spherical,
+    Swirl,
+    Horseshoe,
+    Polar,
+    Handkerchief,
+    Heart,
+    Disc,
+    Spiral,
+    Hyperbolic,
+    Diamond,
+}
+
+impl Variation {
+    pub fn apply(&self, z: Complex<f64>) -> Complex<f64> {
+        let (x, y) = (z.re, z.im);
+        let r2 = z.norm_sqr();
+        let r = r2.sqrt();
+        let theta = x.atan2(y);
+        match self {
+            Variation::Linear => z,
+            Variation::Sinusoidal => Complex::new(x.sin(), y.sin()),
+            Variation::Spherical => z / r2,
+            Variation::Swirl => {
+                Complex::new(x * r2.sin() - y * r2.cos(), x * r2.cos() + y * r2.sin())
+            }
+            Variation::Horseshoe => Complex::new((x - y) * (x + y), 2.0 * x * y) / r,
+            Variation::Polar => Complex::new(theta / PI, r - 1.0),
+            Variation::Handkerchief => r * Complex::new((theta + r).sin(), (theta - r).cos()),
+            Variation::Heart => r * Complex::new((theta * r).sin(), -(theta * r).cos()),
+            Variation::Disc => theta / PI * Complex::new((PI * r).sin(), (PI * r).cos()),
+            Variation::Spiral => Complex::new(theta.cos() + r.sin(), theta.sin() - r.cos()) / r,
+            Variation::Hyperbolic => Complex::new(theta.sin() / r, r * theta.cos()),
+            Variation::Diamond => Complex::new(theta.sin() * r.cos(), theta.cos() * r.sin()),
+        }
+    }
+}
+
+/// One map of a flame: the affine map followed by a weighted blend of variations.  Every
+/// point it produces moves its color index halfway towards `color`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlameTransform {
+    pub affine: Affine,
+    pub probability: f64,
+    pub variations: Vec<(Variation, f64)>,
+    pub color: f64,
+}
+
+impl FlameTransform {
+    pub fn apply(&self, z: Complex<f64>) -> Complex<f64> {
+        let z = self.affine.apply(z);
+        self.variations
+            .iter()
+            .map(|(variation, weight)| weight * variation.apply(z))
+            .sum()
+    }
+}
+
+/// A Scott Draves-style fractal flame: a chaos game over `transforms` that counts how often each
+/// pixel is hit and mixes in the palette colors of the hits.  Pixels are shown by their log
+/// density, brightened by `gamma`; `vibrancy` decides whether the gamma correction is applied to
+/// the density alone (1.0), keeping the colors saturated, or to each channel (0.0).
+#[derive(Debug)]
+pub struct Flame {
+    pub transforms: Vec<FlameTransform>,
+    pub palette: Gradient,
+    pub points: u64,
+    pub seed: u64,
+    pub gamma: f64,
+    pub vibrancy: f64,
+}
+
+impl Flame {
+    /// Sums of the red, green and blue of every hit (from 0 to 255 each) and the number of hits.
+    pub fn density(&self, image: &Image, threads: usize) -> Density<4> {
+        let chunks = self.points.div_ceil(POINTS_PER_CHUNK);
+        let choices = WeightedIndex::new(self.transforms.iter().map(|t| t.probability))
+            .expect("flame probabilities are non-negative and not all zero");
+        let palette: Vec<Rgb<u8>> = (0..256)
+            .map(|i| self.palette.color_at(i as f64 / 255.0).as_rgb())
+            .collect();
+
+        Density::accumulate(&image.size, threads, chunks, |chunk, hits| {
+            let mut rng = chunk_rng(self.seed, chunk);
+            let points = POINTS_PER_CHUNK.min(self.points - chunk * POINTS_PER_CHUNK);
+            let random_point =
+                |rng: &mut StdRng| Complex::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0));
+            let mut z = random_point(&mut rng);
+            let mut color = rng.gen::<f64>();
+            for point in 0..SETTLING_POINTS + points {
+                let transform = &self.transforms[choices.sample(&mut rng)];
+                z = transform.apply(z);
+                color = (color + transform.color) / 2.0;
+                if !z.is_finite() {
+                    // Spherical and friends send the origin to infinity; carry on from elsewhere.
+                    z = random_point(&mut rng);
+                } else if point >= SETTLING_POINTS {
+                    if let Some((col, row)) = image.pixel_at(z) {
+                        let Rgb([red, green, blue]) = palette[(color * 255.0) as usize];
+                        hits.add(col, row, [red as u64, green as u64, blue as u64, 1]);
+                    }
+                }
+            }
+        })
+    }
+
+    fn to_image(&self, density: &Density<4>) -> RgbImage {
+        let max_hits = density.max()[3];
+        ImageBuffer::from_fn(density.width(), density.height(), |col, row| {
+            let [red, green, blue, hits] = density.get(col, row);
+            if hits == 0 {
+                return Rgb([0, 0, 0]);
+            }
+            let alpha = ToneMap::Log.apply(hits, max_hits);
+            let channel = |sum: u64| {
+                let average = sum as f64 / (255.0 * hits as f64);
+                (self.vibrancy * average * alpha.powf(1.0 / self.gamma)
+                    + (1.0 - self.vibrancy) * (average * alpha).powf(1.0 / self.gamma))
+                    as f32
+            };
+            Color::new(channel(red), channel(green), channel(blue)).as_rgb()
+        })
+    }
+}
+
+impl Renderer for Flame {
+    fn render(&self, image: &Image, threads: usize) -> RgbImage {
+        self.to_image(&self.density(image, threads))
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::size::Size;
+
+    fn assert_close(expected: Complex<f64>, actual: Complex<f64>) {
+        assert!(
+            (expected - actual).norm() < 1e-12,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    fn image() -> Image {
+        Image {
+            input_filename: "".to_string(),
+            output_filename: "".to_string(),
+            size: Size {
+                width: 40,
+                height: 40,
+            },
+            upper_left: Complex::new(0.0, 1.0),
+            lower_right: Complex::new(1.0, 0.0),
+        }
+    }
+
+    /// The Sierpinski triangle with each corner its own color.
+    fn sierpinski(variations: Vec<(Variation, f64)>) -> Flame {
+        let height = 3.0f64.sqrt() / 2.0;
+        let corner = |e: f64, f: f64, color: f64| FlameTransform {
+            affine: Affine {
+                a: 0.5,
+                b: 0.0,
+                c: 0.0,
+                d: 0.5,
+                e,
+                f,
+            },
+            probability: 1.0,
+            variations: variations.clone(),
+            color,
+        };
+        Flame {
+            transforms: vec![
+                corner(0.0, 0.0, 0.0),
+                corner(0.5, 0.0, 0.5),
+                corner(0.25, height / 2.0, 1.0),
+            ],
+            palette: Gradient::new(vec![
+                Color::new(1.0, 0.0, 0.0),
+                Color::new(0.0, 1.0, 0.0),
+                Color::new(0.0, 0.0, 1.0),
+            ]),
+            points: 50_000,
+            seed: 5,
+            gamma: 4.0,
+            vibrancy: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_variations() {
+        let z = Complex::new(0.6, 0.8);
+        let theta = 0.6f64.atan2(0.8);
+        assert_close(z, Variation::Linear.apply(z));
+        assert_close(
+            Complex::new(0.6f64.sin(), 0.8f64.sin()),
+            Variation::Sinusoidal.apply(z),
+        );
+        // |z| = 1, so the sphere leaves z alone.
+        assert_close(z, Variation::Spherical.apply(z));
+        assert_close(
+            z * Complex::new(1.0f64.sin(), 1.0f64.cos()),
+            Variation::Swirl.apply(z),
+        );
+        assert_close(z * z, Variation::Horseshoe.apply(z));
+        assert_close(Complex::new(theta / PI, 0.0), Variation::Polar.apply(z));
+        assert_close(
+            Complex::new((theta + 1.0).sin(), (theta - 1.0).cos()),
+            Variation::Handkerchief.apply(z),
+        );
+        assert_close(
+            Complex::new(theta.sin(), -theta.cos()),
+            Variation::Heart.apply(z),
+        );
+        assert_close(Complex::new(0.0, -theta / PI), Variation::Disc.apply(z));
+        assert_close(
+            Complex::new(theta.cos() + 1.0f64.sin(), theta.sin() - 1.0f64.cos()),
+            Variation::Spiral.apply(z),
+        );
+        assert_close(
+            Complex::new(theta.sin(), theta.cos()),
+            Variation::Hyperbolic.apply(z),
+        );
+        assert_close(
+            Complex::new(theta.sin() * 1.0f64.cos(), theta.cos() * 1.0f64.sin()),
+            Variation::Diamond.apply(z),
+        );
+    }
+
+    #[test]
+    fn test_transform_blends_variations() {
+        let transform = FlameTransform {
+            affine: Affine {
+                e: 1.0,
+                ..Affine::default()
+            },
+            probability: 1.0,
+            variations: vec![(Variation::Linear, 0.25), (Variation::Sinusoidal, 0.5)],
+            color: 0.0,
+        };
+        let z = Complex::new(0.5, 1.0);
+        let moved = Complex::new(1.5, 1.0);
+        assert_close(
+            0.25 * moved + 0.5 * Complex::new(1.5f64.sin(), 1.0f64.sin()),
+            transform.apply(z),
+        );
+    }
+
+    #[test]
+    fn test_density_is_reproducible() {
+        let image = image();
+        let flame = sierpinski(vec![(Variation::Linear, 1.0)]);
+        let density = flame.density(&image, 1);
+        for threads in [2, 3] {
+            assert_eq!(density, flame.density(&image, threads));
+        }
+    }
+
+    #[test]
+    fn test_linear_flame_is_the_ifs() {
+        let image = image();
+        let density = sierpinski(vec![(Variation::Linear, 1.0)]).density(&image, 2);
+        let (col, row) = image.pixel_at(Complex::new(0.5, 0.29)).unwrap();
+        assert_eq!([0, 0, 0, 0], density.get(col, row));
+        // Points near a corner mostly came through that corner's map.
+        let (col, row) = image.pixel_at(Complex::new(0.02, 0.01)).unwrap();
+        let [red, green, blue, hits] = density.get(col, row);
+        assert!(hits > 0);
+        assert!(red > green && red > blue);
+    }
+
+    #[test]
+    fn test_infinite_points_restart() {
+        let image = image();
+        let mut flame = sierpinski(vec![(Variation::Spherical, 1.0)]);
+        flame.transforms[0].affine.a = 0.0;
+        flame.transforms[0].affine.d = 0.0;
+        let density = flame.density(&image, 1);
+        assert_ne!(Density::new(&image.size), density);
+    }
+
+    #[test]
+    fn test_to_image() {
+        let size = Size {
+            width: 2,
+            height: 1,
+        };
+        let mut density = Density::new(&size);
+        density.add(0, 0, [255 * 100, 0, 0, 100]);
+        density.add(1, 0, [0, 255 * 3, 0, 9]);
+        let mut flame = sierpinski(vec![]);
+        flame.gamma = 1.0;
+        let image = flame.to_image(&density);
+        assert_eq!(&Rgb([255, 0, 0]), image.get_pixel(0, 0));
+        // A third of the hits were green, at half the log density.
+        assert_eq!(&Rgb([0, 42, 0]), image.get_pixel(1, 0));
+
+        flame.gamma = 2.0;
+        flame.vibrancy = 0.0;
+        let image = flame.to_image(&density);
+        assert_eq!(&Rgb([0, 103, 0]), image.get_pixel(1, 0));
+    }
+}
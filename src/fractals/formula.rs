@@ -0,0 +1,648 @@
+use super::bailout::Bailout;
+use super::escape_time::{escape_time_with_previous, EscapeTime, Iteration};
+use super::plane::Plane;
+use super::power::Power;
+use num_complex::Complex;
+use std::convert::TryFrom;
+
+#[cfg(test)]
+use std::any::Any;
+
+/// Iterates a user-supplied expression, starting from z0 = 0 in the parameter plane.
+#[derive(Debug)]
+pub struct Formula {
+    pub max_iterations: i64,
+    pub expression: Expression,
+    pub plane: Plane,
+    pub bailout: Bailout,
+}
+
+impl EscapeTime for Formula {
+    fn iterate(&self, pixel: &Complex<f64>) -> Iteration {
+        let (z0, c) = self.plane.start(*pixel, Complex::new(0.0, 0.0));
+        // Without a known growth rate, a degree of 1 makes smooth coloring keep the plain count.
+        let degree = self.expression.degree().unwrap_or(1.0);
+        escape_time_with_previous(z0, &self.bailout, self.max_iterations, degree, |z, _| {
+            self.expression.evaluate(z, c, *pixel)
+        })
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Variable {
+    Z,
+    C,
+    Pixel,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+}
+
+/// `abs` takes the absolute value of each part, as the Burning Ship does, rather than the modulus.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Function {
+    Abs,
+    Conj,
+    Re,
+    Im,
+    Exp,
+    Log,
+    Sin,
+    Cos,
+    Pow,
+}
+
+impl Function {
+    fn named(name: &str) -> Option<Function> {
+        match name {
+            "abs" => Some(Function::Abs),
+            "conj" => Some(Function::Conj),
+            "re" => Some(Function::Re),
+            "im" => Some(Function::Im),
+            "exp" => Some(Function::Exp),
+            "log" => Some(Function::Log),
+            "sin" => Some(Function::Sin),
+            "cos" => Some(Function::Cos),
+            "pow" => Some(Function::Pow),
+            _ => None,
+        }
+    }
+
+    fn arity(&self) -> usize {
+        match self {
+            Function::Pow => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// An expression compiled from a formula string, ready to evaluate once per iteration.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expression {
+    Number(Complex<f64>),
+    Variable(Variable),
+    Negate(Box<Expression>),
+    Binary(Operator, Box<Expression>, Box<Expression>),
+    Call(Function, Vec<Expression>),
+}
+
+/// A problem in a formula string; `column` counts characters from 1.
+#[derive(Debug, PartialEq)]
+pub struct FormulaError {
+    pub column: usize,
+    pub message: String,
+}
+
+impl Expression {
+    pub fn parse(input: &str) -> Result<Expression, FormulaError> {
+        let tokens = lex(input)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            end: input.chars().count() + 1,
+        };
+        let expression = parser.expression()?;
+        match parser.peek() {
+            None => Ok(expression),
+            Some(token) => Err(FormulaError {
+                column: token.column,
+                message: format!("unexpected {:?}", token.kind),
+            }),
+        }
+    }
+
+    pub fn evaluate(&self, z: Complex<f64>, c: Complex<f64>, pixel: Complex<f64>) -> Complex<f64> {
+        match self {
+            Expression::Number(n) => *n,
+            Expression::Variable(Variable::Z) => z,
+            Expression::Variable(Variable::C) => c,
+            Expression::Variable(Variable::Pixel) => pixel,
+            Expression::Negate(e) => -e.evaluate(z, c, pixel),
+            Expression::Binary(operator, left, right) => {
+                let left = left.evaluate(z, c, pixel);
+                let right = right.evaluate(z, c, pixel);
+                match operator {
+                    Operator::Add => left + right,
+                    Operator::Subtract => left - right,
+                    Operator::Multiply => left * right,
+                    Operator::Divide => left / right,
+                    Operator::Power => pow(left, right),
+                }
+            }
+            Expression::Call(function, arguments) => {
+                let x = arguments[0].evaluate(z, c, pixel);
+                match function {
+                    Function::Abs => Complex::new(x.re.abs(), x.im.abs()),
+                    Function::Conj => x.conj(),
+                    Function::Re => Complex::new(x.re, 0.0),
+                    Function::Im => Complex::new(x.im, 0.0),
+                    Function::Exp => x.exp(),
+                    Function::Log => x.ln(),
+                    Function::Sin => x.sin(),
+                    Function::Cos => x.cos(),
+                    Function::Pow => pow(x, arguments[1].evaluate(z, c, pixel)),
+                }
+            }
+        }
+    }
+
+    /// How fast the expression grows in z for large z, `|value| ≈ |z|^degree`: 3 for
+    /// `z^3 + c` and infinite for `exp(z)`.  `None` when that can't be told from the expression
+    /// alone, e.g. for `z^z`.
+    pub fn degree(&self) -> Option<f64> {
+        let degree = match self {
+            Expression::Number(_) => 0.0,
+            Expression::Variable(Variable::Z) => 1.0,
+            Expression::Variable(_) => 0.0,
+            Expression::Negate(e) => e.degree()?,
+            Expression::Binary(operator, left, right) => match operator {
+                Operator::Add | Operator::Subtract => left.degree()?.max(right.degree()?),
+                Operator::Multiply => left.degree()? + right.degree()?,
+                Operator::Divide => left.degree()? - right.degree()?,
+                Operator::Power => left.degree()? * right.constant()?.re,
+            },
+            Expression::Call(function, arguments) => {
+                let x = arguments[0].degree()?;
+                match function {
+                    Function::Abs | Function::Conj | Function::Re | Function::Im => x,
+                    Function::Exp | Function::Sin | Function::Cos if x > 0.0 => f64::INFINITY,
+                    Function::Exp | Function::Sin | Function::Cos | Function::Log => 0.0,
+                    Function::Pow => x * arguments[1].constant()?.re,
+                }
+            }
+        };
+        Some(degree).filter(|degree| !degree.is_nan())
+    }
+
+    /// The value of an expression that doesn't depend on any variable.
+    fn constant(&self) -> Option<Complex<f64>> {
+        let zero = Complex::new(0.0, 0.0);
+        self.is_constant().then(|| self.evaluate(zero, zero, zero))
+    }
+
+    fn is_constant(&self) -> bool {
+        match self {
+            Expression::Number(_) => true,
+            Expression::Variable(_) => false,
+            Expression::Negate(e) => e.is_constant(),
+            Expression::Binary(_, left, right) => left.is_constant() && right.is_constant(),
+            Expression::Call(_, arguments) => arguments.iter().all(Expression::is_constant),
+        }
+    }
+}
+
+/// Whole-number exponents go through `powi` so that `z^2` matches `z*z` exactly.
+fn pow(base: Complex<f64>, exponent: Complex<f64>) -> Complex<f64> {
+    let power = if exponent.im == 0.0 && exponent.re.fract() == 0.0 {
+        match i32::try_from(exponent.re as i64) {
+            Ok(n) => Power::Integer(n),
+            Err(_) => Power::Complex(exponent),
+        }
+    } else {
+        Power::Complex(exponent)
+    };
+    power.apply(base)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum TokenKind {
+    Number(f64),
+    Imaginary(f64),
+    Identifier(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Caret,
+    LeftParen,
+    RightParen,
+    Comma,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    column: usize,
+}
+
+fn lex(input: &str) -> Result<Vec<Token>, FormulaError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let column = i + 1;
+        let ch = chars[i];
+        let kind = match ch {
+            ' ' | '\t' => {
+                i += 1;
+                continue;
+            }
+            '+' => TokenKind::Plus,
+            '-' => TokenKind::Minus,
+            '*' => TokenKind::Star,
+            '/' => TokenKind::Slash,
+            '^' => TokenKind::Caret,
+            '(' => TokenKind::LeftParen,
+            ')' => TokenKind::RightParen,
+            ',' => TokenKind::Comma,
+            '0'..='9' | '.' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let value = text.parse::<f64>().map_err(|_| FormulaError {
+                    column,
+                    message: format!("{:?} is not a number", text),
+                })?;
+                if i < chars.len() && chars[i] == 'i' && !is_identifier(chars.get(i + 1)) {
+                    i += 1;
+                    tokens.push(Token {
+                        kind: TokenKind::Imaginary(value),
+                        column,
+                    });
+                } else {
+                    tokens.push(Token {
+                        kind: TokenKind::Number(value),
+                        column,
+                    });
+                }
+                continue;
+            }
+            _ if ch.is_ascii_alphabetic() => {
+                let start = i;
+                while is_identifier(chars.get(i)) {
+                    i += 1;
+                }
+                let name: String = chars[start..i].iter().collect();
+                tokens.push(Token {
+                    kind: if name == "i" {
+                        TokenKind::Imaginary(1.0)
+                    } else {
+                        TokenKind::Identifier(name)
+                    },
+                    column,
+                });
+                continue;
+            }
+            _ => {
+                return Err(FormulaError {
+                    column,
+                    message: format!("unexpected character {:?}", ch),
+                })
+            }
+        };
+        tokens.push(Token { kind, column });
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+fn is_identifier(ch: Option<&char>) -> bool {
+    matches!(ch, Some(ch) if ch.is_ascii_alphanumeric() || *ch == '_')
+}
+
+/// Recursive descent, loosest binding first: sums, products, negation, then right-associative
+/// powers.
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    /// The column just past the input, reported when it ends too early.
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Result<Token, FormulaError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or(FormulaError {
+                column: self.end,
+                message: "unexpected end of formula".to_string(),
+            })?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn accept(&mut self, kind: &TokenKind) -> bool {
+        match self.peek() {
+            Some(token) if token.kind == *kind => {
+                self.position += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, kind: &TokenKind) -> Result<(), FormulaError> {
+        let token = self.next()?;
+        if token.kind == *kind {
+            Ok(())
+        } else {
+            Err(FormulaError {
+                column: token.column,
+                message: format!("expected {:?} but found {:?}", kind, token.kind),
+            })
+        }
+    }
+
+    fn expression(&mut self) -> Result<Expression, FormulaError> {
+        let mut left = self.term()?;
+        loop {
+            let operator = if self.accept(&TokenKind::Plus) {
+                Operator::Add
+            } else if self.accept(&TokenKind::Minus) {
+                Operator::Subtract
+            } else {
+                return Ok(left);
+            };
+            left = Expression::Binary(operator, Box::new(left), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expression, FormulaError> {
+        let mut left = self.unary()?;
+        loop {
+            let operator = if self.accept(&TokenKind::Star) {
+                Operator::Multiply
+            } else if self.accept(&TokenKind::Slash) {
+                Operator::Divide
+            } else {
+                return Ok(left);
+            };
+            left = Expression::Binary(operator, Box::new(left), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expression, FormulaError> {
+        if self.accept(&TokenKind::Minus) {
+            Ok(Expression::Negate(Box::new(self.unary()?)))
+        } else {
+            self.power()
+        }
+    }
+
+    fn power(&mut self) -> Result<Expression, FormulaError> {
+        let base = self.primary()?;
+        if self.accept(&TokenKind::Caret) {
+            let exponent = self.unary()?;
+            Ok(Expression::Binary(
+                Operator::Power,
+                Box::new(base),
+                Box::new(exponent),
+            ))
+        } else {
+            Ok(base)
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expression, FormulaError> {
+        let token = self.next()?;
+        match token.kind {
+            TokenKind::Number(n) => Ok(Expression::Number(Complex::new(n, 0.0))),
+            TokenKind::Imaginary(n) => Ok(Expression::Number(Complex::new(0.0, n))),
+            TokenKind::LeftParen => {
+                let expression = self.expression()?;
+                self.expect(&TokenKind::RightParen)?;
+                Ok(expression)
+            }
+            TokenKind::Identifier(name) => {
+                if self.accept(&TokenKind::LeftParen) {
+                    self.call(&name, token.column)
+                } else {
+                    variable(&name, token.column)
+                }
+            }
+            kind => Err(FormulaError {
+                column: token.column,
+                message: format!("unexpected {:?}", kind),
+            }),
+        }
+    }
+
+    fn call(&mut self, name: &str, column: usize) -> Result<Expression, FormulaError> {
+        let function = Function::named(name).ok_or_else(|| FormulaError {
+            column,
+            message: format!("unknown function {:?}", name),
+        })?;
+        let mut arguments = vec![self.expression()?];
+        while self.accept(&TokenKind::Comma) {
+            arguments.push(self.expression()?);
+        }
+        self.expect(&TokenKind::RightParen)?;
+        if arguments.len() != function.arity() {
+            return Err(FormulaError {
+                column,
+                message: format!(
+                    "{} takes {} argument(s) but was given {}",
+                    name,
+                    function.arity(),
+                    arguments.len()
+                ),
+            });
+        }
+        Ok(Expression::Call(function, arguments))
+    }
+}
+
+fn variable(name: &str, column: usize) -> Result<Expression, FormulaError> {
+    match name {
+        "z" => Ok(Expression::Variable(Variable::Z)),
+        "c" => Ok(Expression::Variable(Variable::C)),
+        "pixel" => Ok(Expression::Variable(Variable::Pixel)),
+        _ => Err(FormulaError {
+            column,
+            message: format!("unknown variable {:?}", name),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::mandelbrot::Mandelbrot;
+    use super::*;
+
+    fn evaluate(input: &str, z: Complex<f64>) -> Complex<f64> {
+        Expression::parse(input).unwrap().evaluate(
+            z,
+            Complex::new(0.5, -1.0),
+            Complex::new(3.0, 0.0),
+        )
+    }
+
+    fn error(input: &str) -> FormulaError {
+        Expression::parse(input).unwrap_err()
+    }
+
+    #[test]
+    fn test_parse_precedence() {
+        let z = Box::new(Expression::Variable(Variable::Z));
+        let c = Box::new(Expression::Variable(Variable::C));
+        let two = Box::new(Expression::Number(Complex::new(2.0, 0.0)));
+
+        assert_eq!(
+            Expression::Binary(
+                Operator::Add,
+                Box::new(Expression::Binary(Operator::Power, z, two)),
+                c
+            ),
+            Expression::parse("z^2 + c").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_evaluate_arithmetic() {
+        let z = Complex::new(1.0, 2.0);
+        assert_eq!(Complex::new(7.0, 0.0), evaluate("1 + 2 * 3", z));
+        assert_eq!(Complex::new(9.0, 0.0), evaluate("(1 + 2) * 3", z));
+        assert_eq!(Complex::new(2.0, 0.0), evaluate("8 / 2 / 2", z));
+        assert_eq!(Complex::new(0.0, 0.0), evaluate("5 - 3 - 2", z));
+        assert_eq!(Complex::new(-4.0, 0.0), evaluate("-2^2", z));
+        assert_eq!(Complex::new(512.0, 0.0), evaluate("2^3^2", z));
+        assert_eq!(Complex::new(0.5, 0.0), evaluate("2^-1", z));
+    }
+
+    #[test]
+    fn test_evaluate_variables_and_literals() {
+        let z = Complex::new(1.0, 2.0);
+        assert_eq!(z, evaluate("z", z));
+        assert_eq!(Complex::new(0.5, -1.0), evaluate("c", z));
+        assert_eq!(Complex::new(3.0, 0.0), evaluate("pixel", z));
+        assert_eq!(Complex::new(0.0, 1.0), evaluate("i", z));
+        assert_eq!(Complex::new(1.5, 0.25), evaluate("1.5 + 0.25i", z));
+        assert_eq!(Complex::new(-3.0, 4.0), evaluate("z*z", z));
+        assert_eq!(evaluate("z*z", z), evaluate("z^2", z));
+    }
+
+    #[test]
+    fn test_evaluate_functions() {
+        let z = Complex::new(-1.0, 2.0);
+        assert_eq!(Complex::new(1.0, 2.0), evaluate("abs(z)", z));
+        assert_eq!(Complex::new(-1.0, -2.0), evaluate("conj(z)", z));
+        assert_eq!(Complex::new(-1.0, 0.0), evaluate("re(z)", z));
+        assert_eq!(Complex::new(2.0, 0.0), evaluate("im(z)", z));
+        assert_eq!(z.exp(), evaluate("exp(z)", z));
+        assert_eq!(z.ln(), evaluate("log(z)", z));
+        assert_eq!(z.sin(), evaluate("sin(z)", z));
+        assert_eq!(z.cos(), evaluate("cos(z)", z));
+        assert_eq!(z * z * z, evaluate("pow(z, 3)", z));
+        assert_eq!(
+            z.powc(Complex::new(0.5, 1.0)),
+            evaluate("pow(z, 0.5 + i)", z)
+        );
+        assert_eq!(
+            z * z * z + Complex::new(0.5, -1.0) * z.sin(),
+            evaluate("z^3 + c*sin(z)", z)
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_report_column() {
+        assert_eq!(5, error("z + $").column);
+        assert_eq!(5, error("z + q").column);
+        assert_eq!(1, error("tan(z)").column);
+        assert_eq!(4, error("z +").column);
+        assert_eq!(3, error("z c").column);
+        assert_eq!(7, error("(z + c").column);
+        assert_eq!(1, error("pow(z)").column);
+        assert_eq!(1, error("1.2.3").column);
+        assert_eq!(1, error("").column);
+    }
+
+    #[test]
+    fn test_degree() {
+        let degree = |input| Expression::parse(input).unwrap().degree();
+        assert_eq!(Some(2.0), degree("z^2 + c"));
+        assert_eq!(Some(2.0), degree("z*z + c"));
+        assert_eq!(Some(3.0), degree("-z^3 + z^2 - pixel"));
+        assert_eq!(Some(2.5), degree("pow(z, 2.5) + c"));
+        assert_eq!(Some(0.5), degree("z^(0.5 + i)"));
+        assert_eq!(Some(4.0), degree("z^(2*2)"));
+        assert_eq!(Some(1.0), degree("(z^2 + c) / (z - 1)"));
+        assert_eq!(Some(2.0), degree("abs(z)^2 + c"));
+        assert_eq!(Some(f64::INFINITY), degree("z^3 + c*sin(z)"));
+        assert_eq!(Some(0.0), degree("exp(c) + log(z)"));
+        assert_eq!(None, degree("z^z"));
+        assert_eq!(None, degree("z^c"));
+        assert_eq!(None, degree("exp(z) / exp(z)"));
+    }
+
+    #[test]
+    fn test_formula_reports_its_degree() {
+        let formula = |input| Formula {
+            max_iterations: 256,
+            expression: Expression::parse(input).unwrap(),
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(2.0),
+        };
+        let pixel = Complex::new(1.2, 0.0);
+        assert!(matches!(
+            formula("z^3 + c").iterate(&pixel),
+            Iteration::Outside { degree, .. } if degree == 3.0
+        ));
+        assert!(matches!(
+            formula("z^z + c").iterate(&pixel),
+            Iteration::Outside { degree, .. } if degree == 1.0
+        ));
+    }
+
+    #[test]
+    fn test_formula_matches_mandelbrot() {
+        let formula = Formula {
+            max_iterations: 256,
+            expression: Expression::parse("z^2 + c").unwrap(),
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(2.0),
+        };
+        let mandelbrot = Mandelbrot {
+            max_iterations: 256,
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(2.0),
+            power: Power::default(),
+        };
+        for pixel in &[
+            Complex::new(0.0, 0.0),
+            Complex::new(-1.0, 0.25),
+            Complex::new(0.2, 0.6),
+            Complex::new(2.0, 2.0),
+        ] {
+            assert_eq!(mandelbrot.iterate(pixel), formula.iterate(pixel));
+        }
+    }
+
+    #[test]
+    fn test_formula_julia_form() {
+        let formula = Formula {
+            max_iterations: 256,
+            expression: Expression::parse("z^2 + c").unwrap(),
+            plane: Plane::Dynamical(Complex::new(0.0, 0.0)),
+            bailout: Bailout::Circle(2.0),
+        };
+        assert!(matches!(
+            formula.iterate(&Complex::new(0.5, 0.5)),
+            Iteration::Inside { .. }
+        ));
+        assert!(matches!(
+            formula.iterate(&Complex::new(1.5, 0.0)),
+            Iteration::Outside { iterations: 1, .. }
+        ));
+    }
+}
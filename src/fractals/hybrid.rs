@@ -0,0 +1,142 @@
+use super::bailout::Bailout;
+use super::escape_time::{escape_time, EscapeTime, Iteration, Step};
+use super::plane::Plane;
+use num_complex::Complex;
+
+#[cfg(test)]
+use std::any::Any;
+
+/// Applies several formulas in a repeating sequence, one per iteration, e.g. a Burning Ship step
+/// followed by two Mandelbrot steps.  The parameter plane starts at z0 = 0.
+#[derive(Debug)]
+pub struct Hybrid {
+    pub max_iterations: i64,
+    pub plane: Plane,
+    pub bailout: Bailout,
+    pub steps: Vec<Box<dyn Step>>,
+}
+
+impl EscapeTime for Hybrid {
+    fn iterate(&self, pixel: &Complex<f64>) -> Iteration {
+        let (z0, c) = self.plane.start(*pixel, Complex::new(0.0, 0.0));
+        escape_time(z0, c, &self.bailout, self.max_iterations, &self.steps)
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::burning_ship::{BurningShipStep, Variant};
+    use super::super::mandelbrot::{Mandelbrot, MandelbrotStep};
+    use super::super::power::Power;
+    use super::*;
+
+    fn mandelbrot(power: i32) -> MandelbrotStep {
+        MandelbrotStep {
+            power: Power::Integer(power),
+        }
+    }
+
+    fn burning_ship() -> BurningShipStep {
+        BurningShipStep {
+            variant: Variant::BurningShip,
+            power: Power::default(),
+        }
+    }
+
+    fn mandelbrot_fractal() -> Mandelbrot {
+        Mandelbrot {
+            max_iterations: 256,
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(2.0),
+            power: Power::default(),
+        }
+    }
+
+    fn hybrid(steps: Vec<Box<dyn Step>>) -> Hybrid {
+        Hybrid {
+            max_iterations: 256,
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(2.0),
+            steps,
+        }
+    }
+
+    #[test]
+    fn test_single_step_matches_its_fractal() {
+        let single = hybrid(vec![Box::new(mandelbrot(2))]);
+        for c in &[
+            Complex::new(0.0, 0.0),
+            Complex::new(0.2, 0.6),
+            Complex::new(-1.0, 0.3),
+            Complex::new(2.0, 2.0),
+        ] {
+            assert_eq!(mandelbrot_fractal().iterate(c), single.iterate(c));
+        }
+    }
+
+    #[test]
+    fn test_squaring_twice_is_a_fourth_power() {
+        let twice = hybrid(vec![Box::new(mandelbrot(2)), Box::new(mandelbrot(2))]);
+        // With c = 0 each pair of steps is z -> z⁴, so the orbits escape in step.
+        let twice_julia = Hybrid {
+            plane: Plane::Dynamical(Complex::new(0.0, 0.0)),
+            ..twice
+        };
+        assert!(matches!(
+            twice_julia.iterate(&Complex::new(1.1, 0.0)),
+            Iteration::Outside { iterations: 3, .. }
+        ));
+        assert!(matches!(
+            twice_julia.iterate(&Complex::new(0.9, 0.3)),
+            Iteration::Inside { .. }
+        ));
+    }
+
+    #[test]
+    fn test_steps_alternate_in_order() {
+        let sequence = hybrid(vec![
+            Box::new(burning_ship()),
+            Box::new(mandelbrot(2)),
+            Box::new(mandelbrot(2)),
+        ]);
+        let c = Complex::new(0.3, -0.5);
+
+        let steps: [&dyn Step; 3] = [&burning_ship(), &mandelbrot(2), &mandelbrot(2)];
+        let mut z = Complex::new(0.0, 0.0);
+        let mut iterations = 0;
+        while z.norm_sqr() < 4.0 && iterations < 256 {
+            z = steps[iterations as usize % 3].apply(z, c);
+            iterations += 1;
+        }
+
+        let expected = if iterations < 256 {
+            Iteration::Outside {
+                iterations,
+                max_iterations: 256,
+                final_z: z,
+                escape_length: 2.0,
+                degree: 2.0,
+            }
+        } else {
+            Iteration::Inside {
+                iterations,
+                max_iterations: 256,
+            }
+        };
+        assert_eq!(expected, sequence.iterate(&c));
+        // c is inside the Mandelbrot set, but every third Burning Ship step knocks it out.
+        assert!(matches!(
+            sequence.iterate(&c),
+            Iteration::Outside { iterations: 10, .. }
+        ));
+        assert!(matches!(
+            mandelbrot_fractal().iterate(&c),
+            Iteration::Inside { .. }
+        ));
+    }
+}
@@ -0,0 +1,174 @@
+use super::affine::Affine;
+use super::density::{chunk_rng, Density, ToneMap};
+use super::image::Image;
+use super::job::Renderer;
+use ::image::RgbImage;
+use num_complex::Complex;
+use rand::distributions::{Distribution, WeightedIndex};
+
+#[cfg(test)]
+use std::any::Any;
+
+/// Points drawn from the generator that `chunk_rng` gives each chunk.
+const POINTS_PER_CHUNK: u64 = 100_000;
+
+/// Points each chunk throws away while its orbit is still making its way to the attractor.
+const SETTLING_POINTS: u64 = 20;
+
+/// One map of an iterated function system and how often the chaos game picks it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    pub affine: Affine,
+    pub probability: f64,
+}
+
+impl Transform {
+    pub fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64, probability: f64) -> Transform {
+        Transform {
+            affine: Affine { a, b, c, d, e, f },
+            probability,
+        }
+    }
+}
+
+/// An iterated function system drawn by the chaos game: a point hops from map to map, picked at
+/// random by their probabilities, and every landing is counted in the density.  The
+/// probabilities are relative weights and need not add up to 1.
+#[derive(Debug)]
+pub struct Ifs {
+    pub transforms: Vec<Transform>,
+    pub points: u64,
+    pub seed: u64,
+    pub tone_map: ToneMap,
+}
+
+impl Ifs {
+    pub fn density(&self, image: &Image, threads: usize) -> Density {
+        let chunks = self.points.div_ceil(POINTS_PER_CHUNK);
+        let choices = WeightedIndex::new(self.transforms.iter().map(|t| t.probability))
+            .expect("IFS probabilities are non-negative and not all zero");
+        Density::accumulate(&image.size, threads, chunks, |chunk, hits| {
+            let mut rng = chunk_rng(self.seed, chunk);
+            let points = POINTS_PER_CHUNK.min(self.points - chunk * POINTS_PER_CHUNK);
+            let mut z = Complex::new(0.0, 0.0);
+            for point in 0..SETTLING_POINTS + points {
+                z = self.transforms[choices.sample(&mut rng)].affine.apply(z);
+                if point >= SETTLING_POINTS {
+                    if let Some((col, row)) = image.pixel_at(z) {
+                        hits.add(col, row, [1, 1, 1]);
+                    }
+                }
+            }
+        })
+    }
+}
+
+impl Renderer for Ifs {
+    fn render(&self, image: &Image, threads: usize) -> RgbImage {
+        self.density(image, threads).to_image(self.tone_map)
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Barnsley's fern, which fits in `-2.2 ≤ x ≤ 2.7` and `0 ≤ y ≤ 10`.
+pub fn barnsley_fern() -> Vec<Transform> {
+    vec![
+        Transform::new(0.0, 0.0, 0.0, 0.16, 0.0, 0.0, 0.01),
+        Transform::new(0.85, 0.04, -0.04, 0.85, 0.0, 1.6, 0.85),
+        Transform::new(0.2, -0.26, 0.23, 0.22, 0.0, 1.6, 0.07),
+        Transform::new(-0.15, 0.28, 0.26, 0.24, 0.0, 0.44, 0.07),
+    ]
+}
+
+/// The Sierpinski triangle with corners 0, 1 and `1/2 + (√3/2)i`.
+pub fn sierpinski() -> Vec<Transform> {
+    let height = 3.0f64.sqrt() / 2.0;
+    vec![
+        Transform::new(0.5, 0.0, 0.0, 0.5, 0.0, 0.0, 1.0),
+        Transform::new(0.5, 0.0, 0.0, 0.5, 0.5, 0.0, 1.0),
+        Transform::new(0.5, 0.0, 0.0, 0.5, 0.25, height / 2.0, 1.0),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractals::size::Size;
+
+    fn image(upper_left: Complex<f64>, lower_right: Complex<f64>) -> Image {
+        Image {
+            input_filename: "".to_string(),
+            output_filename: "".to_string(),
+            size: Size {
+                width: 50,
+                height: 50,
+            },
+            upper_left,
+            lower_right,
+        }
+    }
+
+    fn ifs(transforms: Vec<Transform>, points: u64) -> Ifs {
+        Ifs {
+            transforms,
+            points,
+            seed: 3,
+            tone_map: ToneMap::Log,
+        }
+    }
+
+    fn total(density: &Density, image: &Image) -> u64 {
+        (0..image.size.height)
+            .flat_map(|row| (0..image.size.width).map(move |col| (col, row)))
+            .map(|(col, row)| density.get(col, row)[0])
+            .sum()
+    }
+
+    #[test]
+    fn test_density_is_reproducible() {
+        let image = image(Complex::new(0.0, 1.0), Complex::new(1.0, 0.0));
+        let triangle = ifs(sierpinski(), 250_000);
+        let density = triangle.density(&image, 1);
+        for threads in [2, 3, 7] {
+            assert_eq!(density, triangle.density(&image, threads));
+        }
+        let mut reseeded = ifs(sierpinski(), 250_000);
+        reseeded.seed = 4;
+        assert_ne!(density, reseeded.density(&image, 1));
+    }
+
+    #[test]
+    fn test_sierpinski_has_a_hole() {
+        let image = image(Complex::new(0.0, 1.0), Complex::new(1.0, 0.0));
+        let density = ifs(sierpinski(), 100_000).density(&image, 2);
+        // The middle of the triangle that the three maps leave out.
+        let (col, row) = image.pixel_at(Complex::new(0.5, 0.29)).unwrap();
+        assert_eq!([0, 0, 0], density.get(col, row));
+        // The corners are fixed points of the maps.
+        let (col, row) = image.pixel_at(Complex::new(0.0, 0.0)).unwrap();
+        assert!(density.get(col, row)[0] > 0);
+        assert_eq!(100_000, total(&density, &image));
+    }
+
+    #[test]
+    fn test_fern_stays_in_its_bounds() {
+        let image = image(Complex::new(-2.5, 10.5), Complex::new(3.0, -0.5));
+        let density = ifs(barnsley_fern(), 100_000).density(&image, 2);
+        assert_eq!(100_000, total(&density, &image));
+    }
+
+    #[test]
+    fn test_zero_probability_is_never_picked() {
+        let image = image(Complex::new(0.0, 1.0), Complex::new(1.0, 0.0));
+        let mut transforms = sierpinski();
+        transforms[1].probability = 0.0;
+        transforms[2].probability = 0.0;
+        let density = ifs(transforms, 1_000).density(&image, 1);
+        let (col, row) = image.pixel_at(Complex::new(0.0, 0.0)).unwrap();
+        assert_eq!([1_000; 3], density.get(col, row));
+    }
+}
@@ -1,6 +1,7 @@
 use super::size::Size;
 use ::image::{ImageBuffer, Rgb, RgbImage};
 use num_complex::Complex;
+use std::thread;
 
 #[derive(Debug, PartialEq)]
 pub struct Image {
@@ -12,16 +13,34 @@ pub struct Image {
 }
 
 impl Image {
-    pub fn build(&self, fun: impl Fn(Complex<f64>) -> Rgb<u8>) -> RgbImage {
-        let mut image = ImageBuffer::new(self.size.width as u32, self.size.height as u32);
-        for row in 0..self.size.height {
-            for col in 0..self.size.width {
-                let z = self.complex_at(col, row);
-                let pixel_color = fun(z);
-                let pixel = image.get_pixel_mut(col, row);
-                *pixel = pixel_color;
-            }
+    /// Computes every pixel with `fun`, spreading the rows round-robin across `threads` workers,
+    /// but never more workers than rows.
+    pub fn build(&self, threads: usize, fun: impl Fn(Complex<f64>) -> Rgb<u8> + Sync) -> RgbImage {
+        let mut image: RgbImage = ImageBuffer::new(self.size.width, self.size.height);
+        let row_length = 3 * self.size.width as usize;
+        if row_length == 0 {
+            return image;
+        }
+
+        let threads = threads.min(self.size.height as usize).max(1);
+        let mut bands: Vec<Vec<(u32, &mut [u8])>> = (0..threads).map(|_| Vec::new()).collect();
+        for (row, pixels) in image.chunks_mut(row_length).enumerate() {
+            bands[row % threads].push((row as u32, pixels));
         }
+
+        let fun = &fun;
+        thread::scope(|scope| {
+            for band in bands {
+                scope.spawn(move || {
+                    for (row, pixels) in band {
+                        for (col, pixel) in pixels.chunks_mut(3).enumerate() {
+                            let Rgb(channels) = fun(self.complex_at(col as u32, row));
+                            pixel.copy_from_slice(&channels);
+                        }
+                    }
+                });
+            }
+        });
         image
     }
 
@@ -52,12 +71,26 @@ impl Image {
             self.top() - row as f64 * self.y_delta(),
         )
     }
+
+    /// The `(col, row)` of the pixel nearest to z, or `None` when z is out of view.
+    pub fn pixel_at(&self, z: Complex<f64>) -> Option<(u32, u32)> {
+        let col = ((z.re - self.left()) / self.x_delta()).round();
+        let row = ((self.top() - z.im) / self.y_delta()).round();
+        if col >= 0.0 && row >= 0.0 && col < self.size.width as f64 && row < self.size.height as f64
+        {
+            Some((col as u32, row as u32))
+        } else {
+            None
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use assert_eq_float::*;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
 
     fn image() -> Image {
         Image {
@@ -102,6 +135,67 @@ mod tests {
         assert_eq_float!(0.006266318537859007, image().y_delta());
     }
 
+    #[test]
+    fn test_build_maps_each_pixel() {
+        let image = image();
+        let buffer = image.build(1, |z| Rgb([(z.re > 0.0) as u8, (z.im > 0.0) as u8, 7]));
+        assert_eq!(512, buffer.width());
+        assert_eq!(384, buffer.height());
+        assert_eq!(&Rgb([0, 1, 7]), buffer.get_pixel(0, 0));
+        assert_eq!(&Rgb([1, 0, 7]), buffer.get_pixel(511, 383));
+    }
+
+    #[test]
+    fn test_build_is_identical_across_thread_counts() {
+        let image = image();
+        let fun = |z: Complex<f64>| {
+            Rgb([
+                (z.re * 100.0) as u8,
+                (z.im * 100.0) as u8,
+                (z.norm_sqr() * 50.0) as u8,
+            ])
+        };
+        let single = image.build(1, fun);
+        for threads in [2, 3, 8, 1000] {
+            assert_eq!(single, image.build(threads, fun));
+        }
+    }
+
+    #[test]
+    fn test_build_uses_at_most_one_thread_per_row() {
+        let image = Image {
+            size: Size {
+                width: 4,
+                height: 3,
+            },
+            ..image()
+        };
+        let workers = Mutex::new(HashSet::new());
+        image.build(8, |_| {
+            workers.lock().unwrap().insert(thread::current().id());
+            Rgb([0, 0, 0])
+        });
+        assert_eq!(3, workers.into_inner().unwrap().len());
+    }
+
+    #[test]
+    fn test_pixel_at_inverts_complex_at() {
+        let image = image();
+        for (col, row) in [(0, 0), (5, 128), (300, 19), (511, 383)] {
+            assert_eq!(Some((col, row)), image.pixel_at(image.complex_at(col, row)));
+        }
+    }
+
+    #[test]
+    fn test_pixel_at_out_of_view() {
+        let image = image();
+        assert_eq!(None, image.pixel_at(Complex::new(-2.1, 0.0)));
+        assert_eq!(None, image.pixel_at(Complex::new(1.21, 0.0)));
+        assert_eq!(None, image.pixel_at(Complex::new(0.0, 1.21)));
+        assert_eq!(None, image.pixel_at(Complex::new(0.0, -1.21)));
+        assert_eq!(None, image.pixel_at(Complex::new(f64::NAN, 0.0)));
+    }
+
     #[test]
     fn test_complex_at() {
         let result1 = image().complex_at(5, 128);
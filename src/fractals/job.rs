@@ -3,12 +3,16 @@ use super::escape_time::EscapeTime;
 use super::image::Image;
 use super::parser;
 use super::parser::ParsingError;
+use ::image::RgbImage;
+
+#[cfg(test)]
+use std::any::Any;
 
 #[derive(Debug)]
 pub struct Job {
-    pub fractal: Box<dyn EscapeTime>,
+    pub renderer: Box<dyn Renderer>,
     pub image: Image,
-    pub color_scheme: Box<dyn ColorScheme>,
+    pub threads: usize,
 }
 
 impl Job {
@@ -18,16 +22,42 @@ impl Job {
 
     pub fn generate(self) {
         let Job {
-            fractal,
+            renderer,
             image,
-            color_scheme,
+            threads,
         } = self;
-        let image_buffer = image.build(|z| {
-            let iter = fractal.iterate(&z);
-            let color = color_scheme.color(iter);
-            color.as_rgb()
-        });
+        let image_buffer = renderer.render(&image, threads);
         image_buffer.save(&image.output_filename).unwrap();
         println!("wrote {}", image.output_filename);
     }
 }
+
+/// Turns the view of an `Image` into pixels.
+pub trait Renderer: std::fmt::Debug + Send + Sync {
+    fn render(&self, image: &Image, threads: usize) -> RgbImage;
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// The usual rendering: each pixel is iterated once and colored by how its orbit ended.
+#[derive(Debug)]
+pub struct EscapeTimeRenderer {
+    pub fractal: Box<dyn EscapeTime>,
+    pub color_scheme: Box<dyn ColorScheme>,
+}
+
+impl Renderer for EscapeTimeRenderer {
+    fn render(&self, image: &Image, threads: usize) -> RgbImage {
+        image.build(threads, |z| {
+            let iter = self.fractal.iterate(&z);
+            let color = self.color_scheme.color(iter);
+            color.as_rgb()
+        })
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -0,0 +1,121 @@
+use super::bailout::Bailout;
+use super::escape_time::{escape_time, EscapeTime, Iteration, Step};
+use super::plane::Plane;
+use num_complex::Complex;
+
+#[cfg(test)]
+use std::any::Any;
+
+/// The complex logistic map `λz(1 - z)`, with the plane's c as λ.  The parameter plane starts from
+/// the critical point z0 = 0.5.
+#[derive(Debug)]
+pub struct Lambda {
+    pub max_iterations: i64,
+    pub plane: Plane,
+    pub bailout: Bailout,
+}
+
+impl EscapeTime for Lambda {
+    fn iterate(&self, pixel: &Complex<f64>) -> Iteration {
+        let (z0, lambda) = self.plane.start(*pixel, Complex::new(0.5, 0.0));
+        escape_time(
+            z0,
+            lambda,
+            &self.bailout,
+            self.max_iterations,
+            &[LambdaStep],
+        )
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// One iteration of the logistic map `λz(1 - z)`.
+#[derive(Debug)]
+pub struct LambdaStep;
+
+impl Step for LambdaStep {
+    fn apply(&self, z: Complex<f64>, lambda: Complex<f64>) -> Complex<f64> {
+        lambda * z * (1.0 - z)
+    }
+
+    fn degree(&self) -> f64 {
+        2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lambda(plane: Plane) -> Lambda {
+        Lambda {
+            max_iterations: 256,
+            plane,
+            bailout: Bailout::Circle(100.0),
+        }
+    }
+
+    #[test]
+    fn test_iterate_inside() {
+        // λ = 2 fixes the critical point, λ = 4 sends it 0.5 -> 1 -> 0, and λ = -1.5 has an
+        // attracting two-cycle.
+        for l in &[2.0, 4.0, -1.5] {
+            assert_eq!(
+                Iteration::Inside {
+                    iterations: 256,
+                    max_iterations: 256
+                },
+                lambda(Plane::Parameter).iterate(&Complex::new(*l, 0.0)),
+                "λ = {}",
+                l
+            );
+        }
+    }
+
+    #[test]
+    fn test_iterate_outside() {
+        // 0.5 -> 1.25 -> -1.5625 -> -20.02 -> -2104
+        assert!(matches!(
+            lambda(Plane::Parameter).iterate(&Complex::new(5.0, 0.0)),
+            Iteration::Outside { iterations: 4, .. }
+        ));
+        assert!(matches!(
+            lambda(Plane::Parameter).iterate(&Complex::new(1.0, 3.0)),
+            Iteration::Outside { iterations: 4, .. }
+        ));
+    }
+
+    #[test]
+    fn test_starts_from_critical_point() {
+        // 0.5 is the only point where the derivative λ(1 - 2z) vanishes.
+        let l = Complex::new(2.5, 0.5);
+        let f = |z| LambdaStep.apply(z, l);
+        let h = 1e-6;
+        let z = Complex::new(0.5, 0.0);
+        assert!(((f(z + h) - f(z - h)) / (2.0 * h)).norm() < 1e-9);
+    }
+
+    #[test]
+    fn test_julia() {
+        let julia = lambda(Plane::Dynamical(Complex::new(2.0, 0.0)));
+        assert!(matches!(
+            julia.iterate(&Complex::new(0.5, 0.0)),
+            Iteration::Inside { .. }
+        ));
+        // 3 -> -12 -> -312
+        assert_eq!(
+            Iteration::Outside {
+                iterations: 2,
+                max_iterations: 256,
+                final_z: Complex::new(-312.0, 0.0),
+                escape_length: 100.0,
+                degree: 2.0,
+            },
+            julia.iterate(&Complex::new(3.0, 0.0))
+        );
+    }
+}
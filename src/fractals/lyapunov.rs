@@ -0,0 +1,131 @@
+use super::color_scheme::LyapunovColors;
+use super::image::Image;
+use super::job::Renderer;
+use ::image::RgbImage;
+use num_complex::Complex;
+
+#[cfg(test)]
+use std::any::Any;
+
+/// Which of the two rates drives the logistic map at a step of the rhythm.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rate {
+    A,
+    B,
+}
+
+/// The Lyapunov exponent λ of an orbit: negative where the orbit settles into a cycle (`-∞` for
+/// a superstable one), positive where it is chaotic.  An orbit that runs off to infinity, which
+/// happens for rates outside 0 to 4, has no exponent and gets NaN.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Exponent(pub f64);
+
+/// Markus and Lyapunov's fractal: the logistic map `x = r·x·(1 - x)` with `r` switching between
+/// `a` (the real part of the pixel) and `b` (the imaginary part) following `sequence`.  After
+/// `warmup` steps, the exponent is averaged over the next `iterations`.
+#[derive(Debug, PartialEq)]
+pub struct Lyapunov {
+    pub sequence: Vec<Rate>,
+    pub warmup: u64,
+    pub iterations: u64,
+    pub x0: f64,
+}
+
+impl Lyapunov {
+    pub fn exponent(&self, pixel: Complex<f64>) -> Exponent {
+        let mut rates = self.sequence.iter().cycle().map(|rate| match rate {
+            Rate::A => pixel.re,
+            Rate::B => pixel.im,
+        });
+        let mut x = self.x0;
+        for r in rates.by_ref().take(self.warmup as usize) {
+            x = r * x * (1.0 - x);
+        }
+        let mut sum = 0.0;
+        for r in rates.take(self.iterations as usize) {
+            sum += (r * (1.0 - 2.0 * x)).abs().ln();
+            x = r * x * (1.0 - x);
+            if !x.is_finite() {
+                return Exponent(f64::NAN);
+            }
+        }
+        Exponent(sum / self.iterations.max(1) as f64)
+    }
+}
+
+/// Computes the exponent of each pixel and colors it by stability.
+#[derive(Debug)]
+pub struct LyapunovRenderer {
+    pub fractal: Lyapunov,
+    pub color_scheme: LyapunovColors,
+}
+
+impl Renderer for LyapunovRenderer {
+    fn render(&self, image: &Image, threads: usize) -> RgbImage {
+        image.build(threads, |pixel| {
+            self.color_scheme
+                .color(self.fractal.exponent(pixel))
+                .as_rgb()
+        })
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lyapunov(sequence: Vec<Rate>) -> Lyapunov {
+        Lyapunov {
+            sequence,
+            warmup: 200,
+            iterations: 10_000,
+            x0: 0.3,
+        }
+    }
+
+    fn exponent(sequence: Vec<Rate>, a: f64, b: f64) -> f64 {
+        lyapunov(sequence).exponent(Complex::new(a, b)).0
+    }
+
+    #[test]
+    fn test_superstable() {
+        // x = 1/2 is a fixed point of 2x(1 - x) where the derivative vanishes.
+        let mut fractal = lyapunov(vec![Rate::A]);
+        fractal.x0 = 0.5;
+        assert_eq!(
+            Exponent(f64::NEG_INFINITY),
+            fractal.exponent(Complex::new(2.0, 3.9))
+        );
+        assert!(exponent(vec![Rate::A], 2.0, 3.9) < -5.0);
+    }
+
+    #[test]
+    fn test_stable_cycle() {
+        assert!(exponent(vec![Rate::A], 3.2, 3.9) < -0.5);
+    }
+
+    #[test]
+    fn test_chaos() {
+        // The exponent of 4x(1 - x) is log 2.
+        assert!((exponent(vec![Rate::B], 3.2, 4.0) - 2.0f64.ln()).abs() < 0.02);
+        assert!(exponent(vec![Rate::A], 3.9, 3.2) > 0.0);
+    }
+
+    #[test]
+    fn test_sequence_picks_the_rates() {
+        let ab = exponent(vec![Rate::A, Rate::B], 3.5, 3.8);
+        let ba = exponent(vec![Rate::B, Rate::A], 3.8, 3.5);
+        assert_eq!(ab, ba);
+        assert_ne!(ab, exponent(vec![Rate::A, Rate::A, Rate::B], 3.5, 3.8));
+    }
+
+    #[test]
+    fn test_escaping_orbit_has_no_exponent() {
+        assert!(exponent(vec![Rate::A], 4.5, 3.0).is_nan());
+    }
+}
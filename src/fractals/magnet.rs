@@ -0,0 +1,242 @@
+use super::bailout::Bailout;
+use super::escape_time::{escape_or_converge_time, EscapeTime, Iteration};
+use super::plane::Plane;
+use num_complex::Complex;
+
+#[cfg(test)]
+use std::any::Any;
+
+/// Iterates `((z² + c - 1)/(2z + c - 2))²`.
+#[derive(Debug)]
+pub struct Magnet1 {
+    pub max_iterations: i64,
+    pub plane: Plane,
+    pub epsilon: f64,
+    pub bailout: Bailout,
+}
+
+impl EscapeTime for Magnet1 {
+    fn iterate(&self, pixel: &Complex<f64>) -> Iteration {
+        let (z0, c) = self.plane.start(*pixel, Complex::new(0.0, 0.0));
+        magnet(
+            z0,
+            c,
+            magnet1_step,
+            self.epsilon,
+            &self.bailout,
+            self.max_iterations,
+        )
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Iterates `((z³ + 3(c-1)z + (c-1)(c-2)) / (3z² + 3(c-2)z + (c-1)(c-2) + 1))²`.
+#[derive(Debug)]
+pub struct Magnet2 {
+    pub max_iterations: i64,
+    pub plane: Plane,
+    pub epsilon: f64,
+    pub bailout: Bailout,
+}
+
+impl EscapeTime for Magnet2 {
+    fn iterate(&self, pixel: &Complex<f64>) -> Iteration {
+        let (z0, c) = self.plane.start(*pixel, Complex::new(0.0, 0.0));
+        magnet(
+            z0,
+            c,
+            magnet2_step,
+            self.epsilon,
+            &self.bailout,
+            self.max_iterations,
+        )
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+fn magnet1_step(z: Complex<f64>, c: Complex<f64>) -> Complex<f64> {
+    let q = (z * z + c - 1.0) / (2.0 * z + c - 2.0);
+    q * q
+}
+
+fn magnet2_step(z: Complex<f64>, c: Complex<f64>) -> Complex<f64> {
+    let c1 = c - 1.0;
+    let c2 = c - 2.0;
+    let q = (z * z * z + 3.0 * c1 * z + c1 * c2) / (3.0 * z * z + 3.0 * c2 * z + c1 * c2 + 1.0);
+    q * q
+}
+
+/// Both magnet maps have a fixed point at 1 for every c, so an orbit either escapes, settles
+/// there, or does neither.
+fn magnet(
+    z0: Complex<f64>,
+    c: Complex<f64>,
+    step: fn(Complex<f64>, Complex<f64>) -> Complex<f64>,
+    epsilon: f64,
+    bailout: &Bailout,
+    max_iterations: i64,
+) -> Iteration {
+    let one = Complex::new(1.0, 0.0);
+    let epsilon_sqr = epsilon * epsilon;
+
+    // Both maps behave like a constant times z² for large z.
+    escape_or_converge_time(
+        z0,
+        bailout,
+        max_iterations,
+        2.0,
+        |z| step(z, c),
+        |z, _| {
+            if (z - one).norm_sqr() < epsilon_sqr {
+                Some(0)
+            } else {
+                None
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn magnet1() -> Magnet1 {
+        Magnet1 {
+            max_iterations: 128,
+            plane: Plane::Parameter,
+            epsilon: 1e-6,
+            bailout: Bailout::Circle(100.0),
+        }
+    }
+
+    fn magnet2() -> Magnet2 {
+        Magnet2 {
+            max_iterations: 128,
+            plane: Plane::Parameter,
+            epsilon: 1e-6,
+            bailout: Bailout::Circle(100.0),
+        }
+    }
+
+    #[test]
+    fn test_magnet1_inside() {
+        // With c = 1 the map is (z²/(2z - 1))², which keeps the origin fixed.
+        assert_eq!(
+            Iteration::Inside {
+                iterations: 128,
+                max_iterations: 128
+            },
+            magnet1().iterate(&Complex::new(1.0, 0.0))
+        );
+        assert!(matches!(
+            magnet1().iterate(&Complex::new(0.5, 0.0)),
+            Iteration::Inside { .. }
+        ));
+    }
+
+    #[test]
+    fn test_magnet1_outside() {
+        // z1 = (1.1/0.1)² = 121 is past the escape length straight away.
+        assert!(matches!(
+            magnet1().iterate(&Complex::new(2.1, 0.0)),
+            Iteration::Outside { iterations: 1, .. }
+        ));
+        assert!(matches!(
+            magnet1().iterate(&Complex::new(1.8, 0.0)),
+            Iteration::Outside { iterations: 3, .. }
+        ));
+    }
+
+    #[test]
+    fn test_magnet1_converged() {
+        // z1 = (0.5/-0.5)² lands exactly on the fixed point.
+        assert_eq!(
+            Iteration::Converged {
+                root_index: 0,
+                iterations: 1,
+                max_iterations: 128
+            },
+            magnet1().iterate(&Complex::new(1.5, 0.0))
+        );
+        assert!(matches!(
+            magnet1().iterate(&Complex::new(-2.0, 0.0)),
+            Iteration::Converged { iterations: 5, .. }
+        ));
+    }
+
+    #[test]
+    fn test_magnet2_inside() {
+        assert_eq!(
+            Iteration::Inside {
+                iterations: 128,
+                max_iterations: 128
+            },
+            magnet2().iterate(&Complex::new(1.0, 0.0))
+        );
+        assert!(matches!(
+            magnet2().iterate(&Complex::new(1.5, 0.0)),
+            Iteration::Inside { .. }
+        ));
+    }
+
+    #[test]
+    fn test_magnet2_outside() {
+        assert!(matches!(
+            magnet2().iterate(&Complex::new(1.5, 1.0)),
+            Iteration::Outside { iterations: 3, .. }
+        ));
+    }
+
+    #[test]
+    fn test_magnet2_converged() {
+        assert!(matches!(
+            magnet2().iterate(&Complex::new(-2.0, 0.0)),
+            Iteration::Converged {
+                root_index: 0,
+                iterations: 3,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_julia_forms() {
+        let magnet1_julia = Magnet1 {
+            max_iterations: 128,
+            plane: Plane::Dynamical(Complex::new(0.5, 0.0)),
+            epsilon: 1e-6,
+            bailout: Bailout::Circle(100.0),
+        };
+        assert!(matches!(
+            magnet1_julia.iterate(&Complex::new(0.5, 0.0)),
+            Iteration::Inside { .. }
+        ));
+        assert!(matches!(
+            magnet1_julia.iterate(&Complex::new(3.0, 0.0)),
+            Iteration::Outside { iterations: 6, .. }
+        ));
+
+        let magnet2_julia = Magnet2 {
+            max_iterations: 128,
+            plane: Plane::Dynamical(Complex::new(0.5, 0.0)),
+            epsilon: 1e-6,
+            bailout: Bailout::Circle(100.0),
+        };
+        assert!(matches!(
+            magnet2_julia.iterate(&Complex::new(0.5, 0.0)),
+            Iteration::Inside { .. }
+        ));
+        assert!(matches!(
+            magnet2_julia.iterate(&Complex::new(3.0, 0.0)),
+            Iteration::Converged { iterations: 6, .. }
+        ));
+    }
+}
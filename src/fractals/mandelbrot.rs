@@ -1,4 +1,7 @@
-use super::escape_time::{escape_time, EscapeTime, Iteration};
+use super::bailout::Bailout;
+use super::escape_time::{escape_time, EscapeTime, Iteration, Step};
+use super::plane::Plane;
+use super::power::Power;
 use num_complex::Complex;
 
 #[cfg(test)]
@@ -7,12 +10,21 @@ use std::any::Any;
 #[derive(Debug)]
 pub struct Mandelbrot {
     pub max_iterations: i64,
-    pub escape_length: f64,
+    pub plane: Plane,
+    pub bailout: Bailout,
+    pub power: Power,
 }
 
 impl EscapeTime for Mandelbrot {
-    fn iterate(&self, c: &Complex<f64>) -> Iteration {
-        escape_time(Complex::new(0.0, 0.0), *c, self.escape_length, self.max_iterations, |z| z)
+    fn iterate(&self, pixel: &Complex<f64>) -> Iteration {
+        let (z0, c) = self.plane.start(*pixel, Complex::new(0.0, 0.0));
+        escape_time(
+            z0,
+            c,
+            &self.bailout,
+            self.max_iterations,
+            &[MandelbrotStep { power: self.power }],
+        )
     }
 
     #[cfg(test)]
@@ -21,6 +33,22 @@ impl EscapeTime for Mandelbrot {
     }
 }
 
+/// One iteration of `z^n + c`, on its own so that a `Hybrid` can mix it with other formulas.
+#[derive(Debug)]
+pub struct MandelbrotStep {
+    pub power: Power,
+}
+
+impl Step for MandelbrotStep {
+    fn apply(&self, z: Complex<f64>, c: Complex<f64>) -> Complex<f64> {
+        self.power.apply(z) + c
+    }
+
+    fn degree(&self) -> f64 {
+        self.power.degree()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -29,7 +57,9 @@ mod tests {
     fn test_iterate_inside() {
         let m = Mandelbrot {
             max_iterations: 512,
-            escape_length: 2.0,
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(2.0),
+            power: Power::default(),
         };
 
         assert_eq!(
@@ -52,22 +82,229 @@ mod tests {
     fn test_iterate_outside() {
         let m = Mandelbrot {
             max_iterations: 512,
-            escape_length: 2.0,
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(2.0),
+            power: Power::default(),
         };
 
         assert_eq!(
             Iteration::Outside {
                 iterations: 1,
-                max_iterations: 512
+                max_iterations: 512,
+                final_z: Complex::new(2.0, 2.0),
+                escape_length: 2.0,
+                degree: 2.0,
             },
             m.iterate(&Complex::new(2.0, 2.0))
         );
         assert_eq!(
             Iteration::Outside {
                 iterations: 12,
-                max_iterations: 512
+                max_iterations: 512,
+                final_z: Complex::new(-3.0811421390570404, 2.59180319951574),
+                escape_length: 2.0,
+                degree: 2.0,
             },
             m.iterate(&Complex::new(0.2, 0.6))
         );
     }
+
+    #[test]
+    fn test_iterate_bailout_shapes() {
+        let with_bailout = |bailout| Mandelbrot {
+            max_iterations: 512,
+            plane: Plane::Parameter,
+            bailout,
+            power: Power::default(),
+        };
+        let c = Complex::new(1.2, 1.2);
+
+        // |c| = 1.70 is still inside the circle, but |Re c| + |Im c| = 2.4 is not.
+        assert!(matches!(
+            with_bailout(Bailout::Circle(2.0)).iterate(&c),
+            Iteration::Outside { iterations: 2, .. }
+        ));
+        assert!(matches!(
+            with_bailout(Bailout::L1(2.0)).iterate(&c),
+            Iteration::Outside { iterations: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_iterate_biomorph() {
+        let biomorph = Mandelbrot {
+            max_iterations: 512,
+            plane: Plane::Parameter,
+            bailout: Bailout::Biomorph(2.0),
+            power: Power::default(),
+        };
+
+        // 3i leaves the circle with a small real part, so the biomorph rule keeps it.
+        assert_eq!(
+            Iteration::Inside {
+                iterations: 1,
+                max_iterations: 512
+            },
+            biomorph.iterate(&Complex::new(0.0, 3.0))
+        );
+        assert!(matches!(
+            biomorph.iterate(&Complex::new(2.5, 2.5)),
+            Iteration::Outside { iterations: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_iterate_multibrot() {
+        let m = Mandelbrot {
+            max_iterations: 512,
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(2.0),
+            power: Power::Integer(3),
+        };
+
+        assert!(matches!(
+            m.iterate(&Complex::new(-0.5, 0.0)),
+            Iteration::Outside { iterations: 6, .. }
+        ));
+        assert!(matches!(
+            m.iterate(&Complex::new(0.4, 0.4)),
+            Iteration::Inside { .. }
+        ));
+    }
+
+    #[test]
+    fn test_iterate_real_power_matches_integer_power() {
+        let integer = Mandelbrot {
+            max_iterations: 512,
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(2.0),
+            power: Power::Integer(4),
+        };
+        let real = Mandelbrot {
+            max_iterations: 512,
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(2.0),
+            power: Power::Real(4.0),
+        };
+
+        for c in [Complex::new(0.5, 0.0), Complex::new(0.0, 1.0)] {
+            assert!(matches!(
+                (integer.iterate(&c), real.iterate(&c)),
+                (
+                    Iteration::Outside { iterations: i, .. },
+                    Iteration::Outside { iterations: r, .. }
+                ) if i == r
+            ));
+        }
+    }
+
+    #[test]
+    fn test_julia_iterate_inside() {
+        let m = Mandelbrot {
+            max_iterations: 512,
+            plane: Plane::Dynamical(Complex::new(0.0, 0.0)),
+            bailout: Bailout::Circle(2.0),
+            power: Power::default(),
+        };
+
+        assert_eq!(
+            Iteration::Inside {
+                iterations: 512,
+                max_iterations: 512,
+            },
+            m.iterate(&Complex::new(0.0, 0.0))
+        );
+        assert_eq!(
+            Iteration::Inside {
+                iterations: 512,
+                max_iterations: 512,
+            },
+            m.iterate(&Complex::new(0.2, 0.5))
+        );
+    }
+
+    #[test]
+    fn test_julia_iterate_outside() {
+        let m = Mandelbrot {
+            max_iterations: 512,
+            plane: Plane::Dynamical(Complex::new(0.0, 0.0)),
+            bailout: Bailout::Circle(2.0),
+            power: Power::default(),
+        };
+
+        assert_eq!(
+            Iteration::Outside {
+                iterations: 0,
+                max_iterations: 512,
+                final_z: Complex::new(2.0, 2.0),
+                escape_length: 2.0,
+                degree: 2.0,
+            },
+            m.iterate(&Complex::new(2.0, 2.0))
+        );
+        assert_eq!(
+            Iteration::Outside {
+                iterations: 55,
+                max_iterations: 512,
+                final_z: Complex::new(1.2566712059967413, 1.9996387797107376),
+                escape_length: 2.0,
+                degree: 2.0,
+            },
+            m.iterate(&Complex::new(0.8, 0.6))
+        );
+    }
+
+    #[test]
+    fn test_seeded_z0() {
+        let seeded = |z0| Mandelbrot {
+            max_iterations: 512,
+            plane: Plane::Slice {
+                z0: Some(z0),
+                c: Complex::new(0.0, 0.0),
+                angle: 0.0,
+            },
+            bailout: Bailout::Circle(2.0),
+            power: Power::default(),
+        };
+
+        // 0 is a fixed point of z² + 0, but 1.1 runs away from it.
+        assert!(matches!(
+            seeded(Complex::new(0.0, 0.0)).iterate(&Complex::new(0.0, 0.0)),
+            Iteration::Inside { .. }
+        ));
+        assert!(matches!(
+            seeded(Complex::new(1.1, 0.0)).iterate(&Complex::new(0.0, 0.0)),
+            Iteration::Outside { .. }
+        ));
+    }
+
+    #[test]
+    fn test_julibrot_slice_turns_into_julia_set() {
+        let c = Complex::new(-0.4, 0.6);
+        let julia = Mandelbrot {
+            max_iterations: 512,
+            plane: Plane::Dynamical(c),
+            bailout: Bailout::Circle(2.0),
+            power: Power::default(),
+        };
+        let slice = Mandelbrot {
+            plane: Plane::Slice {
+                z0: None,
+                c,
+                angle: std::f64::consts::FRAC_PI_2,
+            },
+            ..julia
+        };
+
+        for pixel in &[
+            Complex::new(0.0, 0.0),
+            Complex::new(0.3, 0.3),
+            Complex::new(1.0, 1.0),
+        ] {
+            assert_eq!(
+                std::mem::discriminant(&julia.iterate(pixel)),
+                std::mem::discriminant(&slice.iterate(pixel))
+            );
+        }
+    }
 }
@@ -0,0 +1,156 @@
+use super::bailout::Bailout;
+use super::escape_time::{escape_time, EscapeTime, Iteration, Step};
+use super::plane::Plane;
+use super::power::Power;
+use num_complex::Complex;
+
+#[cfg(test)]
+use std::any::Any;
+
+/// McMullen's singular perturbations `z^n + λ/z^m`, with the plane's c as λ.
+///
+/// Besides escaping directly, orbits can fall through the "trap door" around the pole at 0, which
+/// the `λ/z^m` term flings past the bailout on the next step.  Landing on the pole itself counts
+/// as escaping to infinity.  The parameter plane starts from the critical point
+/// `(mλ/n)^(1/(n+m))`; all the critical orbits behave alike by symmetry.
+#[derive(Debug)]
+pub struct McMullen {
+    pub max_iterations: i64,
+    pub plane: Plane,
+    pub n: Power,
+    pub m: Power,
+    pub bailout: Bailout,
+}
+
+impl McMullen {
+    fn critical_point(&self, lambda: Complex<f64>) -> Complex<f64> {
+        let n = self.n.exponent();
+        let m = self.m.exponent();
+        (m * lambda / n).powc(1.0 / (n + m))
+    }
+}
+
+impl EscapeTime for McMullen {
+    fn iterate(&self, pixel: &Complex<f64>) -> Iteration {
+        let (z0, lambda) = self
+            .plane
+            .start_with(*pixel, |lambda| self.critical_point(lambda));
+        escape_time(
+            z0,
+            lambda,
+            &self.bailout,
+            self.max_iterations,
+            &[McMullenStep {
+                n: self.n,
+                m: self.m,
+            }],
+        )
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// One iteration of `z^n + λ/z^m`.
+#[derive(Debug)]
+pub struct McMullenStep {
+    pub n: Power,
+    pub m: Power,
+}
+
+impl Step for McMullenStep {
+    fn apply(&self, z: Complex<f64>, lambda: Complex<f64>) -> Complex<f64> {
+        let pole = self.m.apply(z);
+        if pole.norm_sqr() == 0.0 {
+            Complex::new(f64::INFINITY, 0.0)
+        } else {
+            self.n.apply(z) + lambda / pole
+        }
+    }
+
+    /// For large z the pole term vanishes and z^n is left.
+    fn degree(&self) -> f64 {
+        self.n.degree()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mcmullen(plane: Plane) -> McMullen {
+        McMullen {
+            max_iterations: 256,
+            plane,
+            n: Power::Integer(3),
+            m: Power::Integer(3),
+            bailout: Bailout::Circle(10.0),
+        }
+    }
+
+    fn step() -> McMullenStep {
+        McMullenStep {
+            n: Power::Integer(3),
+            m: Power::Integer(3),
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        let z = Complex::new(1.0, 1.0);
+        let lambda = Complex::new(0.5, 0.0);
+        assert_eq!(z * z * z + lambda / (z * z * z), step().apply(z, lambda));
+    }
+
+    #[test]
+    fn test_critical_point() {
+        let fractal = mcmullen(Plane::Parameter);
+        let lambda = Complex::new(-0.1, 0.2);
+        let f = |z| step().apply(z, lambda);
+        let z = fractal.critical_point(lambda);
+        let h = 1e-6;
+        assert!(((f(z + h) - f(z - h)) / (2.0 * h)).norm() < 1e-8);
+    }
+
+    #[test]
+    fn test_iterate_inside() {
+        assert!(matches!(
+            mcmullen(Plane::Parameter).iterate(&Complex::new(0.1, 0.0)),
+            Iteration::Inside { .. }
+        ));
+        assert!(matches!(
+            mcmullen(Plane::Parameter).iterate(&Complex::new(0.0, 0.25)),
+            Iteration::Inside { .. }
+        ));
+    }
+
+    #[test]
+    fn test_iterate_outside() {
+        assert!(matches!(
+            mcmullen(Plane::Parameter).iterate(&Complex::new(0.5, 0.0)),
+            Iteration::Outside { iterations: 3, .. }
+        ));
+        // A small λ sends the critical orbit through the trap door.
+        assert!(matches!(
+            mcmullen(Plane::Parameter).iterate(&Complex::new(0.01, 0.0)),
+            Iteration::Outside { iterations: 5, .. }
+        ));
+    }
+
+    #[test]
+    fn test_trap_door() {
+        let julia = mcmullen(Plane::Dynamical(Complex::new(-0.01, 0.0)));
+        // 0.01³ - 0.01/0.01³ is far past the bailout in one step.
+        assert!(matches!(
+            julia.iterate(&Complex::new(0.01, 0.0)),
+            Iteration::Outside { iterations: 1, final_z, .. } if final_z.re < -9000.0
+        ));
+        // The pole itself escapes to infinity rather than producing NaN.
+        assert!(matches!(
+            julia.iterate(&Complex::new(0.0, 0.0)),
+            Iteration::Outside { iterations: 1, final_z, .. } if final_z.re == f64::INFINITY
+        ));
+    }
+}
@@ -1,11 +1,32 @@
+pub mod affine;
+mod attractor;
+pub mod bailout;
+mod bifurcation;
+mod buddhabrot;
 pub mod burning_ship;
+mod collatz;
 pub mod color_scheme;
+mod density;
 pub mod escape_time;
+mod flame;
+mod formula;
+mod hybrid;
+mod ifs;
 pub mod image;
 pub mod job;
-mod julia;
+mod lambda;
+mod lyapunov;
+mod magnet;
 mod mandelbrot;
+mod mcmullen;
+mod newton;
+mod nova;
 pub mod parser;
+mod phoenix;
+pub mod plane;
+pub mod power;
 pub mod size;
+mod transcendental;
+mod tricorn;
 
 pub use self::job::Job;
@@ -0,0 +1,274 @@
+use super::escape_time::{EscapeTime, Iteration};
+use num_complex::Complex;
+
+#[cfg(test)]
+use std::any::Any;
+
+/// Newton's method for `polynomial`, starting from the pixel.  Points are colored by the root
+/// they converge to; `roots` fixes the order of the root indexes.
+#[derive(Debug)]
+pub struct Newton {
+    pub max_iterations: i64,
+    pub polynomial: Polynomial,
+    pub roots: Vec<Complex<f64>>,
+    pub epsilon: f64,
+}
+
+impl EscapeTime for Newton {
+    fn iterate(&self, z0: &Complex<f64>) -> Iteration {
+        let epsilon_sqr = self.epsilon * self.epsilon;
+        let mut z = *z0;
+        let mut iterations = 0;
+
+        while iterations < self.max_iterations {
+            if let Some(root_index) = self
+                .roots
+                .iter()
+                .position(|root| (z - root).norm_sqr() < epsilon_sqr)
+            {
+                return Iteration::Converged {
+                    root_index,
+                    iterations,
+                    max_iterations: self.max_iterations,
+                };
+            }
+            let (value, derivative) = self.polynomial.evaluate(z);
+            z -= value / derivative;
+            if !z.is_finite() {
+                break;
+            }
+            iterations += 1;
+        }
+
+        Iteration::Inside {
+            iterations: self.max_iterations,
+            max_iterations: self.max_iterations,
+        }
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// A polynomial with complex coefficients, highest degree first.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Polynomial {
+    coefficients: Vec<Complex<f64>>,
+}
+
+impl Polynomial {
+    /// Leading zeros are dropped; what is left must have a degree of at least one.
+    pub fn new(coefficients: Vec<Complex<f64>>) -> Option<Polynomial> {
+        let leading = coefficients
+            .iter()
+            .position(|a| a.re != 0.0 || a.im != 0.0)?;
+        let coefficients = coefficients[leading..].to_vec();
+        if coefficients.len() < 2 {
+            return None;
+        }
+        Some(Polynomial { coefficients })
+    }
+
+    pub fn from_roots(roots: &[Complex<f64>]) -> Option<Polynomial> {
+        let mut coefficients = vec![Complex::new(1.0, 0.0)];
+        for root in roots {
+            coefficients.push(Complex::new(0.0, 0.0));
+            for i in (1..coefficients.len()).rev() {
+                let previous = coefficients[i - 1];
+                coefficients[i] -= root * previous;
+            }
+        }
+        Polynomial::new(coefficients)
+    }
+
+    pub fn degree(&self) -> usize {
+        self.coefficients.len() - 1
+    }
+
+    /// The value and the derivative at `z`, both by Horner's method.
+    pub fn evaluate(&self, z: Complex<f64>) -> (Complex<f64>, Complex<f64>) {
+        let mut value = Complex::new(0.0, 0.0);
+        let mut derivative = Complex::new(0.0, 0.0);
+        for a in &self.coefficients {
+            derivative = derivative * z + value;
+            value = value * z + a;
+        }
+        (value, derivative)
+    }
+
+    /// Finds all of the roots at once with the Durand-Kerner method.
+    pub fn roots(&self) -> Vec<Complex<f64>> {
+        let leading = self.coefficients[0];
+        let monic = Polynomial {
+            coefficients: self.coefficients.iter().map(|a| a / leading).collect(),
+        };
+        let seed = Complex::new(0.4, 0.9);
+        let mut roots: Vec<Complex<f64>> =
+            (0..self.degree()).map(|k| seed.powu(k as u32)).collect();
+
+        for _ in 0..1000 {
+            let mut largest_change: f64 = 0.0;
+            for i in 0..roots.len() {
+                let denominator = roots
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .fold(Complex::new(1.0, 0.0), |product, (_, root)| {
+                        product * (roots[i] - root)
+                    });
+                let change = monic.evaluate(roots[i]).0 / denominator;
+                roots[i] -= change;
+                largest_change = largest_change.max(change.norm());
+            }
+            if largest_change < 1e-14 {
+                break;
+            }
+        }
+        roots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube_roots_of_unity() -> Vec<Complex<f64>> {
+        (0..3)
+            .map(|k| Complex::from_polar(1.0, 2.0 * std::f64::consts::PI * k as f64 / 3.0))
+            .collect()
+    }
+
+    fn newton() -> Newton {
+        let roots = cube_roots_of_unity();
+        Newton {
+            max_iterations: 64,
+            polynomial: Polynomial::from_roots(&roots).unwrap(),
+            roots,
+            epsilon: 1e-6,
+        }
+    }
+
+    #[test]
+    fn test_iterate_converges_to_nearby_root() {
+        assert_eq!(
+            Iteration::Converged {
+                root_index: 0,
+                iterations: 0,
+                max_iterations: 64
+            },
+            newton().iterate(&Complex::new(1.0, 0.0))
+        );
+        assert!(matches!(
+            newton().iterate(&Complex::new(2.0, 0.1)),
+            Iteration::Converged { root_index: 0, .. }
+        ));
+        assert!(matches!(
+            newton().iterate(&Complex::new(-0.5, 1.0)),
+            Iteration::Converged { root_index: 1, .. }
+        ));
+        assert!(matches!(
+            newton().iterate(&Complex::new(-0.5, -1.0)),
+            Iteration::Converged { root_index: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn test_iterate_counts_steps() {
+        // 2 -> 1.41667 -> 1.11053 -> 1.01064 -> 1.00011 -> 1.00000001 converges quadratically.
+        assert!(matches!(
+            newton().iterate(&Complex::new(2.0, 0.0)),
+            Iteration::Converged {
+                root_index: 0,
+                iterations: 5,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_iterate_critical_point_never_converges() {
+        // The derivative vanishes at 0, so the first step blows up.
+        assert_eq!(
+            Iteration::Inside {
+                iterations: 64,
+                max_iterations: 64
+            },
+            newton().iterate(&Complex::new(0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_polynomial_new_drops_leading_zeros() {
+        let p = Polynomial::new(vec![
+            Complex::new(0.0, 0.0),
+            Complex::new(2.0, 0.0),
+            Complex::new(-1.0, 0.0),
+        ])
+        .unwrap();
+        assert_eq!(1, p.degree());
+    }
+
+    #[test]
+    fn test_polynomial_new_needs_a_variable() {
+        assert_eq!(None, Polynomial::new(vec![]));
+        assert_eq!(None, Polynomial::new(vec![Complex::new(0.0, 0.0)]));
+        assert_eq!(
+            None,
+            Polynomial::new(vec![Complex::new(0.0, 0.0), Complex::new(3.0, 0.0)])
+        );
+    }
+
+    #[test]
+    fn test_polynomial_from_roots() {
+        // (z - 1)(z + 2) = z² + z - 2
+        let p = Polynomial::from_roots(&[Complex::new(1.0, 0.0), Complex::new(-2.0, 0.0)]);
+        assert_eq!(
+            Polynomial::new(vec![
+                Complex::new(1.0, 0.0),
+                Complex::new(1.0, 0.0),
+                Complex::new(-2.0, 0.0)
+            ]),
+            p
+        );
+    }
+
+    #[test]
+    fn test_polynomial_evaluate() {
+        // z³ - 2z + 1 at 2 is 5, and its derivative 3z² - 2 is 10.
+        let p = Polynomial::new(vec![
+            Complex::new(1.0, 0.0),
+            Complex::new(0.0, 0.0),
+            Complex::new(-2.0, 0.0),
+            Complex::new(1.0, 0.0),
+        ])
+        .unwrap();
+        assert_eq!(
+            (Complex::new(5.0, 0.0), Complex::new(10.0, 0.0)),
+            p.evaluate(Complex::new(2.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_polynomial_roots() {
+        let expected = cube_roots_of_unity();
+        let p = Polynomial::new(vec![
+            Complex::new(2.0, 0.0),
+            Complex::new(0.0, 0.0),
+            Complex::new(0.0, 0.0),
+            Complex::new(-2.0, 0.0),
+        ])
+        .unwrap();
+        let roots = p.roots();
+
+        assert_eq!(3, roots.len());
+        for root in expected {
+            let nearest = roots
+                .iter()
+                .map(|r| (r - root).norm())
+                .fold(f64::INFINITY, f64::min);
+            assert!(nearest < 1e-12, "no root near {}", root);
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use super::bailout::Bailout;
+use super::escape_time::{escape_or_converge_time, EscapeTime, Iteration};
+use super::plane::Plane;
+use super::power::Power;
+use num_complex::Complex;
+use std::f64::consts::PI;
+
+#[cfg(test)]
+use std::any::Any;
+
+/// Relaxed Newton's method for `z^n - 1` perturbed by c.  The parameter plane starts at z0 = 1.
+#[derive(Debug)]
+pub struct Nova {
+    pub max_iterations: i64,
+    pub plane: Plane,
+    pub relaxation: Complex<f64>,
+    pub power: Power,
+    pub epsilon: f64,
+    pub bailout: Bailout,
+}
+
+impl EscapeTime for Nova {
+    fn iterate(&self, pixel: &Complex<f64>) -> Iteration {
+        let (z0, c) = self.plane.start(*pixel, Complex::new(1.0, 0.0));
+        nova(
+            z0,
+            c,
+            self.relaxation,
+            &self.power,
+            self.epsilon,
+            &self.bailout,
+            self.max_iterations,
+        )
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Iterates `z - R(z^n - 1)/(n z^(n-1)) + c` until the orbit escapes or stops moving.  A settled
+/// orbit is attributed to the root of unity nearest in angle, which tells the basins apart.
+fn nova(
+    z0: Complex<f64>,
+    c: Complex<f64>,
+    relaxation: Complex<f64>,
+    power: &Power,
+    epsilon: f64,
+    bailout: &Bailout,
+    max_iterations: i64,
+) -> Iteration {
+    let n = power.exponent();
+    // The parser only accepts powers that have one lower.
+    let Some(lowered) = power.lowered() else {
+        return Iteration::Inside {
+            iterations: 0,
+            max_iterations,
+        };
+    };
+    let sectors = n.re.round().max(1.0);
+    let epsilon_sqr = epsilon * epsilon;
+
+    // For large z the step is close to (1 - relaxation / n)·z, so it only grows linearly.
+    escape_or_converge_time(
+        z0,
+        bailout,
+        max_iterations,
+        1.0,
+        |z| {
+            let z_lowered = lowered.apply(z);
+            z - relaxation * (z_lowered * z - 1.0) / (n * z_lowered) + c
+        },
+        |z, z_prev| {
+            if (z - z_prev).norm_sqr() < epsilon_sqr {
+                let sector = (z.arg() * sectors / (2.0 * PI)).round();
+                Some(sector.rem_euclid(sectors) as usize)
+            } else {
+                None
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nova() -> Nova {
+        Nova {
+            max_iterations: 128,
+            plane: Plane::Parameter,
+            relaxation: Complex::new(1.0, 0.0),
+            power: Power::Integer(3),
+            epsilon: 1e-6,
+            bailout: Bailout::Circle(100.0),
+        }
+    }
+
+    fn nova_julia(c: Complex<f64>) -> Nova {
+        Nova {
+            max_iterations: 128,
+            plane: Plane::Dynamical(c),
+            relaxation: Complex::new(1.0, 0.0),
+            power: Power::Integer(3),
+            epsilon: 1e-6,
+            bailout: Bailout::Circle(100.0),
+        }
+    }
+
+    #[test]
+    fn test_iterate_converged_at_root() {
+        // z0 = 1 is already a root of z³ - 1, so with c = 0 the first step stays put.
+        assert_eq!(
+            Iteration::Converged {
+                root_index: 0,
+                iterations: 1,
+                max_iterations: 128
+            },
+            nova().iterate(&Complex::new(0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_iterate_converges_with_perturbation() {
+        assert!(matches!(
+            nova().iterate(&Complex::new(-0.2, 0.1)),
+            Iteration::Converged { .. }
+        ));
+    }
+
+    #[test]
+    fn test_iterate_outside() {
+        // z1 = 1 + c is already beyond the escape length.
+        assert_eq!(
+            Iteration::Outside {
+                iterations: 1,
+                max_iterations: 128,
+                final_z: Complex::new(201.0, 0.0),
+                escape_length: 100.0,
+                degree: 1.0,
+            },
+            nova().iterate(&Complex::new(200.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_julia_basins_by_root() {
+        let julia = nova_julia(Complex::new(0.0, 0.0));
+        assert!(matches!(
+            julia.iterate(&Complex::new(2.0, 0.1)),
+            Iteration::Converged { root_index: 0, .. }
+        ));
+        assert!(matches!(
+            julia.iterate(&Complex::new(-0.5, 0.9)),
+            Iteration::Converged { root_index: 1, .. }
+        ));
+        assert!(matches!(
+            julia.iterate(&Complex::new(-0.5, -0.9)),
+            Iteration::Converged { root_index: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn test_julia_relaxation_slows_convergence() {
+        let plain = nova_julia(Complex::new(0.0, 0.0));
+        let relaxed = Nova {
+            relaxation: Complex::new(0.5, 0.0),
+            ..nova_julia(Complex::new(0.0, 0.0))
+        };
+        let iterations = |iteration| match iteration {
+            Iteration::Converged { iterations, .. } => iterations,
+            _ => panic!("expected {:?} to converge", iteration),
+        };
+        assert!(
+            iterations(plain.iterate(&Complex::new(2.0, 0.1)))
+                < iterations(relaxed.iterate(&Complex::new(2.0, 0.1)))
+        );
+    }
+}
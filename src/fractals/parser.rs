@@ -1,28 +1,56 @@
 use lazy_static::lazy_static;
 use num_complex::Complex;
 use regex::Regex;
+use std::convert::TryFrom;
 use std::fs::File;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 use yaml_rust::{Yaml, YamlLoader};
 
-use super::burning_ship::BurningShip;
+use super::affine::Affine;
+use super::attractor::{Map, StrangeAttractor};
+use super::bailout::Bailout;
+use super::bifurcation::{Bifurcation, RealMap};
+use super::buddhabrot::Buddhabrot;
+use super::burning_ship::{BurningShip, BurningShipStep, Variant};
+use super::collatz::Collatz;
 use super::color_scheme::{
-    BlackOnWhite, Blue, ColorScheme, Gray, Green, Random, Red, WhiteOnBlack,
+    Basins, BlackOnWhite, Blue, Color, ColorScheme, Gradient, GradientScheme, Gray, Green,
+    LyapunovColors, Random, Red, Smooth, Spread, WhiteOnBlack,
 };
-use super::escape_time::EscapeTime;
+use super::density::ToneMap;
+use super::escape_time::{EscapeTime, Step};
+use super::flame::{Flame, FlameTransform, Variation};
+use super::formula::{Expression, Formula};
+use super::hybrid::Hybrid;
+use super::ifs::{self, Ifs, Transform};
 use super::image::Image;
-use super::julia::Julia;
-use super::mandelbrot::Mandelbrot;
+use super::job::{EscapeTimeRenderer, Renderer};
+use super::lambda::Lambda;
+use super::lyapunov::{Lyapunov, LyapunovRenderer, Rate};
+use super::magnet::{Magnet1, Magnet2};
+use super::mandelbrot::{Mandelbrot, MandelbrotStep};
+use super::mcmullen::McMullen;
+use super::newton::{Newton, Polynomial};
+use super::nova::Nova;
+use super::phoenix::Phoenix;
+use super::plane::Plane;
+use super::power::Power;
 use super::size::Size;
+use super::transcendental::{Function, Transcendental, TranscendentalStep};
+use super::tricorn::{Tricorn, TricornStep};
 use super::Job;
 
 #[derive(Debug, PartialEq)]
 pub enum ParsingError {
+    BadBailout(String),
     BadColorScheme(String),
     BadComplexNumber(String),
     BadFloat(String),
+    /// A formula that doesn't parse, with the column of the problem.
+    BadFormula(usize, String),
     BadFractal(String),
+    BadGradient(String),
     BadInteger(String),
     BadSize(String),
     IoError(String),
@@ -53,47 +81,701 @@ pub fn parse(input_filename: &String) -> Result<Job, ParsingError> {
 }
 
 fn parse_job(input_filename: &String, job_yaml: &Yaml) -> Result<Job, ParsingError> {
-    let fractal = parse_fractal(&job_yaml["fractal"])?;
+    let renderer = parse_renderer(job_yaml)?;
     let image = parse_image(input_filename, &job_yaml["image"])?;
-    let color_scheme = parse_color_scheme(&job_yaml["color_scheme"])?;
+    let threads = parse_threads(&job_yaml["threads"])?;
 
     Ok(Job {
-        fractal,
+        renderer,
         image,
-        color_scheme,
+        threads,
     })
 }
 
+/// Density fractals bring their own coloring; everything else is an escape-time fractal drawn
+/// with the `color_scheme`.
+fn parse_renderer(job_yaml: &Yaml) -> Result<Box<dyn Renderer>, ParsingError> {
+    let fractal_yaml = &job_yaml["fractal"];
+    match fractal_yaml["type"].as_str() {
+        Some(fractal_type @ "Buddhabrot") | Some(fractal_type @ "Nebulabrot") => {
+            Ok(Box::new(parse_buddhabrot(fractal_type, fractal_yaml)?))
+        }
+        Some(fractal_type @ "BarnsleyFern")
+        | Some(fractal_type @ "IFS")
+        | Some(fractal_type @ "Sierpinski") => Ok(Box::new(parse_ifs(fractal_type, fractal_yaml)?)),
+        Some("Bifurcation") => Ok(Box::new(parse_bifurcation(fractal_yaml)?)),
+        Some("Clifford") => Ok(Box::new(parse_attractor(Map::Clifford, fractal_yaml)?)),
+        Some("DeJong") => Ok(Box::new(parse_attractor(Map::DeJong, fractal_yaml)?)),
+        Some("Flame") => Ok(Box::new(parse_flame(fractal_yaml)?)),
+        Some("Henon") => Ok(Box::new(parse_attractor(Map::Henon, fractal_yaml)?)),
+        Some("Lyapunov") => Ok(Box::new(LyapunovRenderer {
+            fractal: parse_lyapunov(fractal_yaml)?,
+            color_scheme: parse_lyapunov_colors(&job_yaml["color_scheme"])?,
+        })),
+        _ => Ok(Box::new(EscapeTimeRenderer {
+            fractal: parse_fractal(fractal_yaml)?,
+            color_scheme: parse_color_scheme(&job_yaml["color_scheme"])?,
+        })),
+    }
+}
+
+/// `Buddhabrot` takes one `max_iterations` for all three channels, `Nebulabrot` a list of three;
+/// either accepts both.  The orbits follow the same `formulas:` as a `Hybrid`, just `Mandelbrot`
+/// by default.
+fn parse_buddhabrot(fractal_type: &str, fractal_yaml: &Yaml) -> Result<Buddhabrot, ParsingError> {
+    let plane = parse_plane(fractal_type, fractal_yaml)?;
+    let max_iterations = match fractal_type {
+        "Nebulabrot" => parse_channel_limits(&fractal_yaml["max_iterations"], [5000, 500, 50])?,
+        _ => parse_channel_limits(&fractal_yaml["max_iterations"], [1000; 3])?,
+    };
+    let bailout = parse_bailout(
+        &fractal_yaml["bailout"],
+        Bailout::Circle,
+        parse_escape_length(&fractal_yaml["escapeLength"])?,
+    )?;
+    let steps: Vec<Box<dyn Step>> = match &fractal_yaml["formulas"] {
+        Yaml::BadValue => vec![Box::new(MandelbrotStep {
+            power: Power::default(),
+        })],
+        Yaml::Array(formulas) if !formulas.is_empty() => formulas
+            .iter()
+            .map(parse_step)
+            .collect::<Result<Vec<_>, _>>()?,
+        formulas_yaml => {
+            return Err(ParsingError::BadFractal(format!(
+                "{:?} is not a list of formulas",
+                formulas_yaml
+            )))
+        }
+    };
+    let sample_radius = match &fractal_yaml["sampleRadius"] {
+        Yaml::BadValue => 2.0,
+        radius_yaml => match parse_float(radius_yaml)? {
+            radius if radius > 0.0 => radius,
+            radius => {
+                return Err(ParsingError::BadFloat(format!(
+                    "sampleRadius must be positive, got {}",
+                    radius
+                )))
+            }
+        },
+    };
+    Ok(Buddhabrot {
+        max_iterations,
+        plane,
+        bailout,
+        steps,
+        samples: parse_count(&fractal_yaml["samples"], 1_000_000)?,
+        sample_radius,
+        seed: parse_count(&fractal_yaml["seed"], 0)?,
+        tone_map: parse_tone_map(&fractal_yaml["tone"], ToneMap::Sqrt)?,
+    })
+}
+
+/// `BarnsleyFern` and `Sierpinski` come with their transforms; an `IFS` needs a `transforms:` list.
+fn parse_ifs(fractal_type: &str, fractal_yaml: &Yaml) -> Result<Ifs, ParsingError> {
+    let transforms = match (fractal_type, &fractal_yaml["transforms"]) {
+        ("BarnsleyFern", Yaml::BadValue) => ifs::barnsley_fern(),
+        ("Sierpinski", Yaml::BadValue) => ifs::sierpinski(),
+        (_, Yaml::Array(transforms)) if !transforms.is_empty() => transforms
+            .iter()
+            .map(parse_transform)
+            .collect::<Result<Vec<_>, _>>()?,
+        (_, Yaml::BadValue) => return Err(ParsingError::MissingField("transforms".to_string())),
+        (_, transforms_yaml) => {
+            return Err(ParsingError::BadFractal(format!(
+                "{:?} is not a list of transforms",
+                transforms_yaml
+            )))
+        }
+    };
+    if transforms
+        .iter()
+        .all(|transform| transform.probability == 0.0)
+    {
+        return Err(ParsingError::BadFractal(
+            "at least one transform needs a positive probability".to_string(),
+        ));
+    }
+    Ok(Ifs {
+        transforms,
+        points: parse_count(&fractal_yaml["points"], 1_000_000)?,
+        seed: parse_count(&fractal_yaml["seed"], 0)?,
+        tone_map: parse_tone_map(&fractal_yaml["tone"], ToneMap::Sqrt)?,
+    })
+}
+
+/// A transform is a mapping of the affine coefficients `a` through `f`, which default to the
+/// identity, and a `probability`, which defaults to 1.
+fn parse_transform(transform_yaml: &Yaml) -> Result<Transform, ParsingError> {
+    if transform_yaml.as_hash().is_none() {
+        return Err(ParsingError::BadFractal(format!(
+            "{:?} is not a transform",
+            transform_yaml
+        )));
+    }
+    let coefficient = |name: &str, default: f64| match &transform_yaml[name] {
+        Yaml::BadValue => Ok(default),
+        coefficient_yaml => parse_float(coefficient_yaml),
+    };
+    let identity = Affine::default();
+    let probability = coefficient("probability", 1.0)?;
+    if !probability.is_finite() || probability < 0.0 {
+        return Err(ParsingError::BadFloat(format!(
+            "probability must be a non-negative number, got {}",
+            probability
+        )));
+    }
+    Ok(Transform {
+        affine: Affine {
+            a: coefficient("a", identity.a)?,
+            b: coefficient("b", identity.b)?,
+            c: coefficient("c", identity.c)?,
+            d: coefficient("d", identity.d)?,
+            e: coefficient("e", identity.e)?,
+            f: coefficient("f", identity.f)?,
+        },
+        probability,
+    })
+}
+
+/// `a` through `d` default to parameters with a well-known picture.
+fn parse_attractor(map: Map, fractal_yaml: &Yaml) -> Result<StrangeAttractor, ParsingError> {
+    let defaults = match map {
+        Map::Clifford => [-1.4, 1.6, 1.0, 0.7],
+        Map::DeJong => [1.4, -2.3, 2.4, -2.1],
+        Map::Henon => [1.4, 0.3, 0.0, 0.0],
+    };
+    let parameter = |name: &str, default: f64| match &fractal_yaml[name] {
+        Yaml::BadValue => Ok(default),
+        parameter_yaml => parse_float(parameter_yaml),
+    };
+    Ok(StrangeAttractor {
+        map,
+        a: parameter("a", defaults[0])?,
+        b: parameter("b", defaults[1])?,
+        c: parameter("c", defaults[2])?,
+        d: parameter("d", defaults[3])?,
+        points: parse_count(&fractal_yaml["points"], 1_000_000)?,
+        seed: parse_count(&fractal_yaml["seed"], 0)?,
+        gradient: match &fractal_yaml["gradient"] {
+            Yaml::BadValue => {
+                Gradient::new(vec![Color::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0)])
+            }
+            gradient_yaml => parse_gradient(gradient_yaml)?,
+        },
+        tone_map: parse_tone_map(&fractal_yaml["tone"], ToneMap::Log)?,
+    })
+}
+
+/// The `map` is `logistic` unless given; orbits start from `x0`, 0.5 for most maps but 0 for
+/// the `quadratic` one.
+fn parse_bifurcation(fractal_yaml: &Yaml) -> Result<Bifurcation, ParsingError> {
+    let map = match &fractal_yaml["map"] {
+        Yaml::BadValue => RealMap::Logistic,
+        map_yaml => match map_yaml.as_str() {
+            Some("logistic") => RealMap::Logistic,
+            Some("sine") => RealMap::Sine,
+            Some("tent") => RealMap::Tent,
+            Some("quadratic") => RealMap::Quadratic,
+            _ => {
+                return Err(ParsingError::BadFractal(format!(
+                    "{:?} is not a valid map (logistic, sine, tent or quadratic)",
+                    map_yaml
+                )))
+            }
+        },
+    };
+    Ok(Bifurcation {
+        map,
+        x0: match &fractal_yaml["x0"] {
+            Yaml::BadValue if map == RealMap::Quadratic => 0.0,
+            Yaml::BadValue => 0.5,
+            x0_yaml => parse_float(x0_yaml)?,
+        },
+        transient: parse_count(&fractal_yaml["transient"], 1000)?,
+        points: parse_count(&fractal_yaml["points"], 1000)?,
+        samples: match parse_count(&fractal_yaml["samples"], 4)? {
+            0 => {
+                return Err(ParsingError::BadInteger(
+                    "samples must be positive".to_string(),
+                ))
+            }
+            samples => samples,
+        },
+        gradient: match &fractal_yaml["gradient"] {
+            Yaml::BadValue => {
+                Gradient::new(vec![Color::new(1.0, 1.0, 1.0), Color::new(0.0, 0.0, 0.0)])
+            }
+            gradient_yaml => parse_gradient(gradient_yaml)?,
+        },
+        tone_map: parse_tone_map(&fractal_yaml["tone"], ToneMap::Log)?,
+    })
+}
+
+fn parse_flame(fractal_yaml: &Yaml) -> Result<Flame, ParsingError> {
+    let transforms = match &fractal_yaml["transforms"] {
+        Yaml::Array(transforms) if !transforms.is_empty() => transforms
+            .iter()
+            .enumerate()
+            .map(|(index, transform_yaml)| {
+                parse_flame_transform(transform_yaml, index, transforms.len())
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        Yaml::BadValue => return Err(ParsingError::MissingField("transforms".to_string())),
+        transforms_yaml => {
+            return Err(ParsingError::BadFractal(format!(
+                "{:?} is not a list of transforms",
+                transforms_yaml
+            )))
+        }
+    };
+    if transforms
+        .iter()
+        .all(|transform| transform.probability == 0.0)
+    {
+        return Err(ParsingError::BadFractal(
+            "at least one transform needs a positive probability".to_string(),
+        ));
+    }
+    Ok(Flame {
+        transforms,
+        palette: parse_gradient(&fractal_yaml["palette"])?,
+        points: parse_count(&fractal_yaml["points"], 1_000_000)?,
+        seed: parse_count(&fractal_yaml["seed"], 0)?,
+        gamma: match &fractal_yaml["gamma"] {
+            Yaml::BadValue => 4.0,
+            gamma_yaml => match parse_float(gamma_yaml)? {
+                gamma if gamma > 0.0 => gamma,
+                gamma => {
+                    return Err(ParsingError::BadFloat(format!(
+                        "gamma must be positive, got {}",
+                        gamma
+                    )))
+                }
+            },
+        },
+        vibrancy: match &fractal_yaml["vibrancy"] {
+            Yaml::BadValue => 1.0,
+            vibrancy_yaml => match parse_float(vibrancy_yaml)? {
+                vibrancy if (0.0..=1.0).contains(&vibrancy) => vibrancy,
+                vibrancy => {
+                    return Err(ParsingError::BadFloat(format!(
+                        "vibrancy must be between 0.0 and 1.0, got {}",
+                        vibrancy
+                    )))
+                }
+            },
+        },
+    })
+}
+
+/// The `sequence` is a string of `A`s and `B`s, the rhythm in which the rates alternate.
+fn parse_lyapunov(fractal_yaml: &Yaml) -> Result<Lyapunov, ParsingError> {
+    let sequence = match &fractal_yaml["sequence"] {
+        Yaml::BadValue => vec![Rate::A, Rate::B],
+        Yaml::String(s) => parse_sequence(s)?,
+        sequence_yaml => {
+            return Err(ParsingError::BadFractal(format!(
+                "{:?} is not a sequence of A and B",
+                sequence_yaml
+            )))
+        }
+    };
+    let x0 = match &fractal_yaml["x0"] {
+        Yaml::BadValue => 0.5,
+        x0_yaml => parse_float(x0_yaml)?,
+    };
+    // From the critical point 0.5, the first step without warmup would have a slope of 0.
+    let warmup = match parse_count(&fractal_yaml["warmup"], 200)? {
+        0 if x0 == 0.5 => {
+            return Err(ParsingError::BadInteger(
+                "warmup must be positive when x0 is 0.5".to_string(),
+            ))
+        }
+        warmup => warmup,
+    };
+    Ok(Lyapunov {
+        sequence,
+        warmup,
+        iterations: match parse_count(&fractal_yaml["iterations"], 1000)? {
+            0 => {
+                return Err(ParsingError::BadInteger(
+                    "iterations must be positive".to_string(),
+                ))
+            }
+            iterations => iterations,
+        },
+        x0,
+    })
+}
+
+fn parse_sequence(sequence: &str) -> Result<Vec<Rate>, ParsingError> {
+    let rates = sequence
+        .chars()
+        .map(|rate| match rate {
+            'A' | 'a' => Some(Rate::A),
+            'B' | 'b' => Some(Rate::B),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>();
+    match rates {
+        Some(rates) if !rates.is_empty() => Ok(rates),
+        _ => Err(ParsingError::BadFractal(format!(
+            "{:?} is not a sequence of A and B",
+            sequence
+        ))),
+    }
+}
+
+/// A flame transform is an IFS transform plus a mapping of `variations` to their weights (just
+/// `linear` by default) and a `color` index into the palette.  Without colors, the transforms
+/// are spread evenly over the palette.
+fn parse_flame_transform(
+    transform_yaml: &Yaml,
+    index: usize,
+    count: usize,
+) -> Result<FlameTransform, ParsingError> {
+    let Transform {
+        affine,
+        probability,
+    } = parse_transform(transform_yaml)?;
+    let variations = match &transform_yaml["variations"] {
+        Yaml::BadValue => vec![(Variation::Linear, 1.0)],
+        Yaml::Hash(variations) => variations
+            .iter()
+            .map(|(name, weight)| Ok((parse_variation(name)?, parse_float(weight)?)))
+            .collect::<Result<Vec<_>, ParsingError>>()?,
+        variations_yaml => {
+            return Err(ParsingError::BadFractal(format!(
+                "{:?} is not a mapping of variations to weights",
+                variations_yaml
+            )))
+        }
+    };
+    let color = match &transform_yaml["color"] {
+        Yaml::BadValue => index as f64 / (count - 1).max(1) as f64,
+        color_yaml => match parse_float(color_yaml)? {
+            color if (0.0..=1.0).contains(&color) => color,
+            color => {
+                return Err(ParsingError::BadFloat(format!(
+                    "color {} is not between 0.0 and 1.0",
+                    color
+                )))
+            }
+        },
+    };
+    Ok(FlameTransform {
+        affine,
+        probability,
+        variations,
+        color,
+    })
+}
+
+fn parse_variation(variation_yaml: &Yaml) -> Result<Variation, ParsingError> {
+    match variation_yaml.as_str() {
+        Some("linear") => Ok(Variation::Linear),
+        Some("sinusoidal") => Ok(Variation::Sinusoidal),
+        Some("spherical") => Ok(Variation::Spherical),
+        Some("swirl") => Ok(Variation::Swirl),
+        Some("horseshoe") => Ok(Variation::Horseshoe),
+        Some("polar") => Ok(Variation::Polar),
+        Some("handkerchief") => Ok(Variation::Handkerchief),
+        Some("heart") => Ok(Variation::Heart),
+        Some("disc") => Ok(Variation::Disc),
+        Some("spiral") => Ok(Variation::Spiral),
+        Some("hyperbolic") => Ok(Variation::Hyperbolic),
+        Some("diamond") => Ok(Variation::Diamond),
+        _ => Err(ParsingError::BadFractal(format!(
+            "{:?} is not a valid variation (linear, sinusoidal, spherical, swirl, horseshoe, polar, handkerchief, heart, disc, spiral, hyperbolic or diamond)",
+            variation_yaml
+        ))),
+    }
+}
+
+/// Iteration limits for the red, green and blue channels: a single number for all three or a
+/// list of three, each of them positive.
+fn parse_channel_limits(limits_yaml: &Yaml, default: [i64; 3]) -> Result<[i64; 3], ParsingError> {
+    let limits = match limits_yaml {
+        Yaml::BadValue => default,
+        Yaml::Integer(i) => [*i; 3],
+        Yaml::Array(limits) if limits.len() == 3 => {
+            let mut channels = [0; 3];
+            for (channel, limit_yaml) in channels.iter_mut().zip(limits) {
+                *channel = limit_yaml
+                    .as_i64()
+                    .ok_or_else(|| ParsingError::BadInteger(format!("{:?}", limit_yaml)))?;
+            }
+            channels
+        }
+        _ => {
+            return Err(ParsingError::BadInteger(format!(
+                "{:?} is not an iteration limit or a list of three",
+                limits_yaml
+            )))
+        }
+    };
+    if limits.iter().any(|limit| *limit <= 0) {
+        return Err(ParsingError::BadInteger(format!(
+            "iteration limits must be positive, got {:?}",
+            limits
+        )));
+    }
+    Ok(limits)
+}
+
+fn parse_count(count_yaml: &Yaml, default: u64) -> Result<u64, ParsingError> {
+    match count_yaml {
+        Yaml::BadValue => Ok(default),
+        Yaml::Integer(i) if *i >= 0 => Ok(*i as u64),
+        _ => Err(ParsingError::BadInteger(format!("{:?}", count_yaml))),
+    }
+}
+
+fn parse_tone_map(tone_yaml: &Yaml, default: ToneMap) -> Result<ToneMap, ParsingError> {
+    match tone_yaml {
+        Yaml::BadValue => Ok(default),
+        Yaml::String(s) if s == "linear" => Ok(ToneMap::Linear),
+        Yaml::String(s) if s == "sqrt" => Ok(ToneMap::Sqrt),
+        Yaml::String(s) if s == "log" => Ok(ToneMap::Log),
+        _ => Err(ParsingError::BadFractal(format!(
+            "{:?} is not a valid tone (linear, sqrt or log)",
+            tone_yaml
+        ))),
+    }
+}
+
 fn parse_fractal(fractal_yaml: &Yaml) -> Result<Box<dyn EscapeTime>, ParsingError> {
     let fractal_type = fractal_yaml["type"]
         .as_str()
         .ok_or_else(|| ParsingError::MissingField("fractal type".to_string()))?;
 
     match fractal_type {
-        "BurningShip" => {
+        "BurningShip" | "Buffalo" | "Celtic" | "HeartMandelbrot" | "PerpendicularBurningShip" => {
+            let variant = burning_ship_variant(fractal_type).unwrap_or(Variant::BurningShip);
+            let plane = parse_plane(fractal_type, fractal_yaml)?;
             let max_iterations = parse_max_iterations(&fractal_yaml["max_iterations"])?;
-            let escape_length = parse_escape_length(&fractal_yaml["escapeLength"])?;
+            let bailout = parse_bailout(
+                &fractal_yaml["bailout"],
+                Bailout::Circle,
+                parse_escape_length(&fractal_yaml["escapeLength"])?,
+            )?;
+            let power = parse_power(&fractal_yaml["power"])?;
             Ok(Box::new(BurningShip {
                 max_iterations,
-                escape_length,
+                variant,
+                plane,
+                bailout,
+                power,
+            }))
+        }
+        "Collatz" => {
+            let max_iterations = parse_max_iterations(&fractal_yaml["max_iterations"])?;
+            let bailout = parse_bailout(
+                &fractal_yaml["bailout"],
+                Bailout::Circle,
+                parse_escape_length_or(&fractal_yaml["escapeLength"], 1e10)?,
+            )?;
+            Ok(Box::new(Collatz {
+                max_iterations,
+                bailout,
+            }))
+        }
+        "Formula" => {
+            let plane = parse_plane(fractal_type, fractal_yaml)?;
+            let max_iterations = parse_max_iterations(&fractal_yaml["max_iterations"])?;
+            let expression = parse_formula(&fractal_yaml["formula"])?;
+            let bailout = parse_bailout(
+                &fractal_yaml["bailout"],
+                Bailout::Circle,
+                parse_escape_length(&fractal_yaml["escapeLength"])?,
+            )?;
+            Ok(Box::new(Formula {
+                max_iterations,
+                plane,
+                expression,
+                bailout,
+            }))
+        }
+        "Hybrid" => {
+            let plane = parse_plane(fractal_type, fractal_yaml)?;
+            let max_iterations = parse_max_iterations(&fractal_yaml["max_iterations"])?;
+            let bailout = parse_bailout(
+                &fractal_yaml["bailout"],
+                Bailout::Circle,
+                parse_escape_length(&fractal_yaml["escapeLength"])?,
+            )?;
+            let steps = match fractal_yaml["formulas"].as_vec() {
+                Some(formulas) if !formulas.is_empty() => formulas
+                    .iter()
+                    .map(parse_step)
+                    .collect::<Result<Vec<_>, _>>()?,
+                _ => return Err(ParsingError::MissingField("formulas".to_string())),
+            };
+            Ok(Box::new(Hybrid {
+                max_iterations,
+                plane,
+                bailout,
+                steps,
+            }))
+        }
+        "Lambda" | "LambdaJulia" => {
+            let plane = parse_plane(fractal_type, fractal_yaml)?;
+            let max_iterations = parse_max_iterations(&fractal_yaml["max_iterations"])?;
+            let bailout = parse_bailout(
+                &fractal_yaml["bailout"],
+                Bailout::Circle,
+                parse_escape_length_or(&fractal_yaml["escapeLength"], 100.0)?,
+            )?;
+            Ok(Box::new(Lambda {
+                max_iterations,
+                plane,
+                bailout,
+            }))
+        }
+        "Magnet1" | "Magnet1Julia" => {
+            let plane = parse_plane(fractal_type, fractal_yaml)?;
+            let max_iterations = parse_max_iterations(&fractal_yaml["max_iterations"])?;
+            let epsilon = parse_epsilon(&fractal_yaml["epsilon"])?;
+            let bailout = parse_bailout(
+                &fractal_yaml["bailout"],
+                Bailout::Circle,
+                parse_escape_length_or(&fractal_yaml["escapeLength"], 100.0)?,
+            )?;
+            Ok(Box::new(Magnet1 {
+                max_iterations,
+                plane,
+                epsilon,
+                bailout,
             }))
         }
-        "Julia" => {
-            let c = parse_complex(&fractal_yaml["c"])?;
+        "Magnet2" | "Magnet2Julia" => {
+            let plane = parse_plane(fractal_type, fractal_yaml)?;
             let max_iterations = parse_max_iterations(&fractal_yaml["max_iterations"])?;
-            let escape_length = parse_escape_length(&fractal_yaml["escapeLength"])?;
-            Ok(Box::new(Julia {
+            let epsilon = parse_epsilon(&fractal_yaml["epsilon"])?;
+            let bailout = parse_bailout(
+                &fractal_yaml["bailout"],
+                Bailout::Circle,
+                parse_escape_length_or(&fractal_yaml["escapeLength"], 100.0)?,
+            )?;
+            Ok(Box::new(Magnet2 {
                 max_iterations,
-                c,
-                escape_length,
+                plane,
+                epsilon,
+                bailout,
             }))
         }
-        "Mandelbrot" => {
+        "Mandelbrot" | "Julia" => {
+            let plane = parse_plane(fractal_type, fractal_yaml)?;
             let max_iterations = parse_max_iterations(&fractal_yaml["max_iterations"])?;
-            let escape_length = parse_escape_length(&fractal_yaml["escapeLength"])?;
+            let bailout = parse_bailout(
+                &fractal_yaml["bailout"],
+                Bailout::Circle,
+                parse_escape_length(&fractal_yaml["escapeLength"])?,
+            )?;
+            let power = parse_power(&fractal_yaml["power"])?;
             Ok(Box::new(Mandelbrot {
                 max_iterations,
-                escape_length,
+                plane,
+                bailout,
+                power,
+            }))
+        }
+        "McMullen" | "McMullenJulia" => {
+            let plane = parse_plane(fractal_type, fractal_yaml)?;
+            let max_iterations = parse_max_iterations(&fractal_yaml["max_iterations"])?;
+            let (n, m) = parse_mcmullen_powers(fractal_yaml)?;
+            let bailout = parse_bailout(
+                &fractal_yaml["bailout"],
+                Bailout::Circle,
+                parse_escape_length_or(&fractal_yaml["escapeLength"], 10.0)?,
+            )?;
+            Ok(Box::new(McMullen {
+                max_iterations,
+                plane,
+                n,
+                m,
+                bailout,
+            }))
+        }
+        "Newton" => {
+            let (polynomial, roots) = parse_polynomial(fractal_yaml)?;
+            let max_iterations = parse_max_iterations(&fractal_yaml["max_iterations"])?;
+            let epsilon = parse_epsilon(&fractal_yaml["epsilon"])?;
+            Ok(Box::new(Newton {
+                max_iterations,
+                polynomial,
+                roots,
+                epsilon,
+            }))
+        }
+        "Nova" | "NovaJulia" => {
+            let plane = parse_plane(fractal_type, fractal_yaml)?;
+            let max_iterations = parse_max_iterations(&fractal_yaml["max_iterations"])?;
+            let relaxation = parse_relaxation(&fractal_yaml["relaxation"])?;
+            let power = parse_nova_power(&fractal_yaml["power"])?;
+            let epsilon = parse_epsilon(&fractal_yaml["epsilon"])?;
+            let bailout = parse_bailout(
+                &fractal_yaml["bailout"],
+                Bailout::Circle,
+                parse_escape_length_or(&fractal_yaml["escapeLength"], 1.0e3)?,
+            )?;
+            Ok(Box::new(Nova {
+                max_iterations,
+                plane,
+                relaxation,
+                power,
+                epsilon,
+                bailout,
+            }))
+        }
+        "Phoenix" | "PhoenixJulia" => {
+            let plane = parse_plane(fractal_type, fractal_yaml)?;
+            let p = parse_complex(&fractal_yaml["p"])?;
+            let max_iterations = parse_max_iterations(&fractal_yaml["max_iterations"])?;
+            let bailout = parse_bailout(
+                &fractal_yaml["bailout"],
+                Bailout::Circle,
+                parse_escape_length(&fractal_yaml["escapeLength"])?,
+            )?;
+            Ok(Box::new(Phoenix {
+                max_iterations,
+                plane,
+                p,
+                bailout,
+            }))
+        }
+        "Transcendental" | "TranscendentalJulia" => {
+            let plane = parse_plane(fractal_type, fractal_yaml)?;
+            let max_iterations = parse_max_iterations(&fractal_yaml["max_iterations"])?;
+            let function = parse_function(&fractal_yaml["function"])?;
+            let bailout = parse_bailout(
+                &fractal_yaml["bailout"],
+                |limit| function.bailout(limit),
+                parse_escape_length_or(&fractal_yaml["escapeLength"], 50.0)?,
+            )?;
+            Ok(Box::new(Transcendental {
+                max_iterations,
+                plane,
+                function,
+                bailout,
+            }))
+        }
+        "Tricorn" | "TricornJulia" => {
+            let plane = parse_plane(fractal_type, fractal_yaml)?;
+            let max_iterations = parse_max_iterations(&fractal_yaml["max_iterations"])?;
+            let bailout = parse_bailout(
+                &fractal_yaml["bailout"],
+                Bailout::Circle,
+                parse_escape_length(&fractal_yaml["escapeLength"])?,
+            )?;
+            let power = parse_power(&fractal_yaml["power"])?;
+            Ok(Box::new(Tricorn {
+                max_iterations,
+                plane,
+                bailout,
+                power,
             }))
         }
         _ => Err(ParsingError::BadFractal(format!(
@@ -114,14 +796,307 @@ fn parse_max_iterations(max_iterations_yaml: &Yaml) -> Result<i64, ParsingError>
     }
 }
 
+/// A polynomial is given either by its roots or by its coefficients (highest degree first).
+fn parse_polynomial(fractal_yaml: &Yaml) -> Result<(Polynomial, Vec<Complex<f64>>), ParsingError> {
+    let bad_polynomial = || {
+        ParsingError::BadFractal(format!(
+            "{:?} does not define a polynomial of degree one or more",
+            fractal_yaml
+        ))
+    };
+    match (&fractal_yaml["roots"], &fractal_yaml["coefficients"]) {
+        (Yaml::Array(roots), Yaml::BadValue) => {
+            let roots = roots
+                .iter()
+                .map(parse_complex_or_float)
+                .collect::<Result<Vec<Complex<f64>>, ParsingError>>()?;
+            let polynomial = Polynomial::from_roots(&roots).ok_or_else(bad_polynomial)?;
+            Ok((polynomial, roots))
+        }
+        (Yaml::BadValue, Yaml::Array(coefficients)) => {
+            let coefficients = coefficients
+                .iter()
+                .map(parse_complex_or_float)
+                .collect::<Result<Vec<Complex<f64>>, ParsingError>>()?;
+            let polynomial = Polynomial::new(coefficients).ok_or_else(bad_polynomial)?;
+            let roots = polynomial.roots();
+            Ok((polynomial, roots))
+        }
+        (Yaml::BadValue, Yaml::BadValue) => Err(ParsingError::MissingField(
+            "roots or coefficients".to_string(),
+        )),
+        _ => Err(ParsingError::BadFractal(
+            "expected a list of either roots or coefficients".to_string(),
+        )),
+    }
+}
+
+fn parse_epsilon(epsilon_yaml: &Yaml) -> Result<f64, ParsingError> {
+    match epsilon_yaml {
+        Yaml::BadValue => Ok(1e-6),
+        _ => match parse_float(epsilon_yaml)? {
+            epsilon if epsilon > 0.0 => Ok(epsilon),
+            epsilon => Err(ParsingError::BadFloat(format!(
+                "epsilon must be positive, got {}",
+                epsilon
+            ))),
+        },
+    }
+}
+
+fn parse_power(power_yaml: &Yaml) -> Result<Power, ParsingError> {
+    match power_yaml {
+        Yaml::BadValue => Ok(Power::default()),
+        Yaml::Integer(i) => i32::try_from(*i)
+            .map(Power::Integer)
+            .map_err(|_| ParsingError::BadInteger(format!("power {} is too large", i))),
+        Yaml::Real(_) => {
+            let p = parse_float(power_yaml)?;
+            if p.fract() == 0.0 && p.abs() <= i32::MAX as f64 {
+                Ok(Power::Integer(p as i32))
+            } else {
+                Ok(Power::Real(p))
+            }
+        }
+        _ => parse_complex(power_yaml).map(Power::Complex),
+    }
+}
+
+/// A `bailout:` section is either just a shape or a mapping with a `type` and `radius`; the radius
+/// falls back to `escapeLength`.
+fn parse_bailout(
+    bailout_yaml: &Yaml,
+    default_shape: impl Fn(f64) -> Bailout,
+    escape_length: f64,
+) -> Result<Bailout, ParsingError> {
+    match bailout_yaml {
+        Yaml::BadValue => Ok(default_shape(escape_length)),
+        Yaml::String(_) => Ok(parse_bailout_shape(bailout_yaml)?(escape_length)),
+        Yaml::Hash(_) => {
+            let shape = match &bailout_yaml["type"] {
+                Yaml::BadValue => {
+                    return Err(ParsingError::MissingField("bailout type".to_string()))
+                }
+                shape_yaml => parse_bailout_shape(shape_yaml)?,
+            };
+            let radius = match &bailout_yaml["radius"] {
+                Yaml::BadValue => escape_length,
+                radius_yaml => parse_float(radius_yaml)?,
+            };
+            Ok(shape(radius))
+        }
+        _ => Err(ParsingError::BadBailout(format!(
+            "{:?} is not a valid bailout",
+            bailout_yaml
+        ))),
+    }
+}
+
+fn parse_bailout_shape(shape_yaml: &Yaml) -> Result<fn(f64) -> Bailout, ParsingError> {
+    match shape_yaml {
+        Yaml::String(s) if s == "circle" => Ok(Bailout::Circle),
+        Yaml::String(s) if s == "square" => Ok(Bailout::Square),
+        Yaml::String(s) if s == "l1" => Ok(Bailout::L1),
+        Yaml::String(s) if s == "real" => Ok(Bailout::Real),
+        Yaml::String(s) if s == "imaginary" => Ok(Bailout::Imaginary),
+        Yaml::String(s) if s == "biomorph" => Ok(Bailout::Biomorph),
+        _ => Err(ParsingError::BadBailout(format!(
+            "{:?} is not a valid bailout type (circle, square, l1, real, imaginary or biomorph)",
+            shape_yaml
+        ))),
+    }
+}
+
 fn parse_escape_length(escape_length_yaml: &Yaml) -> Result<f64, ParsingError> {
+    parse_escape_length_or(escape_length_yaml, 2.0)
+}
+
+fn parse_escape_length_or(escape_length_yaml: &Yaml, default: f64) -> Result<f64, ParsingError> {
     match escape_length_yaml {
+        Yaml::BadValue => Ok(default),
+        _ => parse_float(escape_length_yaml),
+    }
+}
+
+fn burning_ship_variant(fractal_type: &str) -> Option<Variant> {
+    match fractal_type {
+        "BurningShip" => Some(Variant::BurningShip),
+        "Buffalo" => Some(Variant::Buffalo),
+        "Celtic" => Some(Variant::Celtic),
+        "HeartMandelbrot" => Some(Variant::Heart),
+        "PerpendicularBurningShip" => Some(Variant::Perpendicular),
+        _ => None,
+    }
+}
+
+/// One formula of a `Hybrid`, either just a type name or a mapping with the `type` and that
+/// family's own parameters.  The hybrid's plane and bailout are shared by all of them.
+fn parse_step(step_yaml: &Yaml) -> Result<Box<dyn Step>, ParsingError> {
+    let step_type = match step_yaml {
+        Yaml::String(s) => s.as_str(),
+        _ => step_yaml["type"]
+            .as_str()
+            .ok_or_else(|| ParsingError::MissingField("formula type".to_string()))?,
+    };
+
+    match step_type {
+        "Mandelbrot" => Ok(Box::new(MandelbrotStep {
+            power: parse_power(&step_yaml["power"])?,
+        })),
+        "Transcendental" => Ok(Box::new(TranscendentalStep {
+            function: parse_function(&step_yaml["function"])?,
+        })),
+        "Tricorn" => Ok(Box::new(TricornStep {
+            power: parse_power(&step_yaml["power"])?,
+        })),
+        _ => match burning_ship_variant(step_type) {
+            Some(variant) => Ok(Box::new(BurningShipStep {
+                variant,
+                power: parse_power(&step_yaml["power"])?,
+            })),
+            None => Err(ParsingError::BadFractal(format!(
+                "{} can't be part of a Hybrid (Mandelbrot, Tricorn, Transcendental or a Burning Ship)",
+                step_type
+            ))),
+        },
+    }
+}
+
+/// `plane: parameter|dynamical|slice`, where the dynamical plane needs `c`.  The `...Julia` type
+/// names default to the dynamical plane.  A `z0:` seed or a slice `angle:` (in degrees) turn the
+/// parameter plane into a slice; `c:` then offsets c.  Keys that the chosen plane doesn't use are
+/// an error rather than silently ignored.
+fn parse_plane(fractal_type: &str, fractal_yaml: &Yaml) -> Result<Plane, ParsingError> {
+    let plane = match &fractal_yaml["plane"] {
+        Yaml::BadValue if fractal_type.ends_with("Julia") => "dynamical",
+        Yaml::BadValue => "parameter",
+        Yaml::String(s) if s == "parameter" || s == "dynamical" || s == "slice" => s.as_str(),
+        plane_yaml => {
+            return Err(ParsingError::BadFractal(format!(
+                "{:?} is not a valid plane (parameter, dynamical or slice)",
+                plane_yaml
+            )))
+        }
+    };
+    let z0_yaml = &fractal_yaml["z0"];
+    let angle_yaml = &fractal_yaml["angle"];
+
+    if plane == "dynamical" {
+        if !z0_yaml.is_badvalue() || !angle_yaml.is_badvalue() {
+            return Err(ParsingError::BadFractal(
+                "z0 and angle don't apply to the dynamical plane, where z starts at the pixel"
+                    .to_string(),
+            ));
+        }
+        Ok(Plane::Dynamical(parse_complex(&fractal_yaml["c"])?))
+    } else if plane == "slice" || !z0_yaml.is_badvalue() || !angle_yaml.is_badvalue() {
+        let z0 = match z0_yaml {
+            Yaml::BadValue => None,
+            _ => Some(parse_complex_or_float(z0_yaml)?),
+        };
+        let c = match &fractal_yaml["c"] {
+            Yaml::BadValue => Complex::new(0.0, 0.0),
+            c_yaml => parse_complex_or_float(c_yaml)?,
+        };
+        let angle = match angle_yaml {
+            Yaml::BadValue => 0.0,
+            _ => parse_float(angle_yaml)?.to_radians(),
+        };
+        Ok(Plane::Slice { z0, c, angle })
+    } else if !fractal_yaml["c"].is_badvalue() {
+        Err(ParsingError::BadFractal(
+            "c only applies to the dynamical plane or a slice, not the parameter plane".to_string(),
+        ))
+    } else {
+        Ok(Plane::Parameter)
+    }
+}
+
+fn parse_formula(formula_yaml: &Yaml) -> Result<Expression, ParsingError> {
+    let formula = formula_yaml
+        .as_str()
+        .ok_or_else(|| ParsingError::MissingField("formula".to_string()))?;
+    Expression::parse(formula).map_err(|e| ParsingError::BadFormula(e.column, e.message))
+}
+
+fn parse_function(function_yaml: &Yaml) -> Result<Function, ParsingError> {
+    match function_yaml {
+        Yaml::BadValue => Err(ParsingError::MissingField("function".to_string())),
+        Yaml::String(s) if s == "sin" => Ok(Function::Sin),
+        Yaml::String(s) if s == "cos" => Ok(Function::Cos),
+        Yaml::String(s) if s == "exp" => Ok(Function::Exp),
+        Yaml::String(s) if s == "sinh" => Ok(Function::Sinh),
+        Yaml::String(s) if s == "cosh" => Ok(Function::Cosh),
+        _ => Err(ParsingError::BadFractal(format!(
+            "{:?} is not a valid function (sin, cos, exp, sinh or cosh)",
+            function_yaml
+        ))),
+    }
+}
+
+/// `z³ + λ/z³` is the family with Sierpinski carpet Julia sets.  The critical point
+/// `(mλ/n)^(1/(n+m))` needs n ≠ 0 and n + m ≠ 0.
+fn parse_mcmullen_powers(fractal_yaml: &Yaml) -> Result<(Power, Power), ParsingError> {
+    let power = |power_yaml: &Yaml| match power_yaml {
+        Yaml::BadValue => Ok(Power::Integer(3)),
+        _ => parse_power(power_yaml),
+    };
+    let n = power(&fractal_yaml["n"])?;
+    let m = power(&fractal_yaml["m"])?;
+    if n.exponent().norm() == 0.0 {
+        return Err(ParsingError::BadFractal("n must not be 0".to_string()));
+    }
+    if (n.exponent() + m.exponent()).norm() == 0.0 {
+        return Err(ParsingError::BadFractal(format!(
+            "n + m must not be 0, got n = {:?} and m = {:?}",
+            n, m
+        )));
+    }
+    Ok((n, m))
+}
+
+/// Nova fractals are usually drawn for `z³ - 1`.  Newton's step for `z^n - 1` divides by
+/// `n z^(n-1)`, which is 0 for n = 0, and for n = 1 it sends every z straight to the root 1.
+/// Both are rejected whether they are written as an integer, a real or a complex number.
+fn parse_nova_power(power_yaml: &Yaml) -> Result<Power, ParsingError> {
+    let power = match power_yaml {
+        Yaml::BadValue => return Ok(Power::Integer(3)),
+        _ => parse_power(power_yaml)?,
+    };
+    let exponent = power.exponent();
+    if exponent == Complex::new(0.0, 0.0) || exponent == Complex::new(1.0, 0.0) {
+        return Err(ParsingError::BadFractal(format!(
+            "power must not be 0 or 1, got {:?}",
+            power_yaml
+        )));
+    }
+    Ok(power)
+}
+
+fn parse_relaxation(relaxation_yaml: &Yaml) -> Result<Complex<f64>, ParsingError> {
+    match relaxation_yaml {
+        Yaml::BadValue => Ok(Complex::new(1.0, 0.0)),
+        _ => parse_complex_or_float(relaxation_yaml),
+    }
+}
+
+fn parse_float(float_yaml: &Yaml) -> Result<f64, ParsingError> {
+    match float_yaml {
         Yaml::Real(s) => s
             .parse::<f64>()
             .map_err(|_| ParsingError::BadFloat(format!("Invalid float: {}", s))),
         Yaml::Integer(i) => Ok(*i as f64),
-        Yaml::BadValue => Ok(2.0),
-        _ => Err(ParsingError::BadFloat(format!("{:?}", escape_length_yaml))),
+        _ => Err(ParsingError::BadFloat(format!("{:?}", float_yaml))),
+    }
+}
+
+fn parse_threads(threads_yaml: &Yaml) -> Result<usize, ParsingError> {
+    match threads_yaml {
+        Yaml::Integer(i) if *i > 0 => Ok(*i as usize),
+        Yaml::BadValue => Ok(std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)),
+        _ => Err(ParsingError::BadInteger(format!("{:?}", threads_yaml))),
     }
 }
 
@@ -190,6 +1165,13 @@ fn parse_u64(i64_value: &Yaml) -> Result<i64, ParsingError> {
     }
 }
 
+fn parse_complex_or_float(value: &Yaml) -> Result<Complex<f64>, ParsingError> {
+    match value {
+        Yaml::Integer(_) | Yaml::Real(_) => Ok(Complex::new(parse_float(value)?, 0.0)),
+        _ => parse_complex(value),
+    }
+}
+
 fn parse_complex(complex_value: &Yaml) -> Result<Complex<f64>, ParsingError> {
     let input = complex_value
         .as_str()
@@ -256,12 +1238,28 @@ fn parse_color_scheme(color_scheme_yaml: &Yaml) -> Result<Box<dyn ColorScheme>,
         .ok_or_else(|| ParsingError::MissingField("color_scheme type".to_string()))?;
 
     match scheme_type {
+        "Basins" => Ok(Box::new(Basins {})),
         "BlackOnWhite" => Ok(Box::new(BlackOnWhite {})),
         "Blue" => Ok(Box::new(Blue {})),
+        "Gradient" => Ok(Box::new(GradientScheme {
+            gradient: parse_stops(&color_scheme_yaml["stops"])?,
+            spread: parse_spread(&color_scheme_yaml["mode"])?,
+            offset: match &color_scheme_yaml["offset"] {
+                Yaml::BadValue => 0.0,
+                offset => parse_float(offset)?,
+            },
+            density: match &color_scheme_yaml["density"] {
+                Yaml::BadValue => None,
+                density => Some(parse_float(density)?),
+            },
+        })),
         "Gray" => Ok(Box::new(Gray {})),
         "Green" => Ok(Box::new(Green {})),
         "Random" => Ok(Box::new(Random::new())),
         "Red" => Ok(Box::new(Red {})),
+        "Smooth" => Ok(Box::new(Smooth {
+            gradient: parse_gradient(&color_scheme_yaml["gradient"])?,
+        })),
         "WhiteOnBlack" => Ok(Box::new(WhiteOnBlack {})),
         _ => Err(ParsingError::BadColorScheme(format!(
             "{} is not a valid color scheme",
@@ -270,6 +1268,124 @@ fn parse_color_scheme(color_scheme_yaml: &Yaml) -> Result<Box<dyn ColorScheme>,
     }
 }
 
+/// The color scheme is optional for a `Lyapunov` fractal, and so is each of its gradients.
+fn parse_lyapunov_colors(color_scheme_yaml: &Yaml) -> Result<LyapunovColors, ParsingError> {
+    match color_scheme_yaml["type"].as_str() {
+        None | Some("Lyapunov") => {}
+        Some(scheme_type) => {
+            return Err(ParsingError::BadColorScheme(format!(
+                "{} is not a color scheme for Lyapunov fractals",
+                scheme_type
+            )))
+        }
+    }
+    let defaults = LyapunovColors::default();
+    Ok(LyapunovColors {
+        stable: match &color_scheme_yaml["stable"] {
+            Yaml::BadValue => defaults.stable,
+            stable_yaml => parse_gradient(stable_yaml)?,
+        },
+        chaotic: match &color_scheme_yaml["chaotic"] {
+            Yaml::BadValue => defaults.chaotic,
+            chaotic_yaml => parse_gradient(chaotic_yaml)?,
+        },
+    })
+}
+
+fn parse_gradient(gradient_yaml: &Yaml) -> Result<Gradient, ParsingError> {
+    match gradient_yaml {
+        Yaml::BadValue => Ok(Gradient::default()),
+        Yaml::Array(colors) if !colors.is_empty() => Ok(Gradient::new(
+            colors
+                .iter()
+                .map(parse_color)
+                .collect::<Result<Vec<Color>, ParsingError>>()?,
+        )),
+        _ => Err(ParsingError::BadGradient(format!(
+            "gradient must be a non-empty list of colors, got {:?}",
+            gradient_yaml
+        ))),
+    }
+}
+
+fn parse_stops(stops_yaml: &Yaml) -> Result<Gradient, ParsingError> {
+    let stops = match stops_yaml {
+        Yaml::Array(stops) if !stops.is_empty() => stops
+            .iter()
+            .map(parse_stop)
+            .collect::<Result<Vec<(f64, Color)>, ParsingError>>()?,
+        _ => {
+            return Err(ParsingError::BadGradient(format!(
+                "stops must be a non-empty list, got {:?}",
+                stops_yaml
+            )))
+        }
+    };
+    if let Some(pair) = stops.windows(2).find(|pair| pair[0].0 > pair[1].0) {
+        return Err(ParsingError::BadGradient(format!(
+            "stop positions must not decrease, got {} before {}",
+            pair[0].0, pair[1].0
+        )));
+    }
+    Ok(Gradient::from_stops(stops))
+}
+
+fn parse_stop(stop_yaml: &Yaml) -> Result<(f64, Color), ParsingError> {
+    let position = parse_float(&stop_yaml["position"]).map_err(|_| {
+        ParsingError::BadGradient(format!("{:?} needs a numeric position", stop_yaml))
+    })?;
+    if !(0.0..=1.0).contains(&position) {
+        return Err(ParsingError::BadGradient(format!(
+            "stop position {} is not between 0.0 and 1.0",
+            position
+        )));
+    }
+    Ok((position, parse_color(&stop_yaml["color"])?))
+}
+
+fn parse_spread(spread_yaml: &Yaml) -> Result<Spread, ParsingError> {
+    match spread_yaml {
+        Yaml::BadValue => Ok(Spread::Clamp),
+        Yaml::String(s) if s == "clamp" => Ok(Spread::Clamp),
+        Yaml::String(s) if s == "repeat" => Ok(Spread::Repeat),
+        Yaml::String(s) if s == "mirror" => Ok(Spread::Mirror),
+        _ => Err(ParsingError::BadGradient(format!(
+            "{:?} is not a valid mode (clamp, repeat or mirror)",
+            spread_yaml
+        ))),
+    }
+}
+
+/// Colors are either `"#rrggbb"` or a list of three floats from 0.0 to 1.0.
+fn parse_color(color_yaml: &Yaml) -> Result<Color, ParsingError> {
+    let bad_color = || ParsingError::BadGradient(format!("{:?} is not a valid color", color_yaml));
+    match color_yaml {
+        Yaml::String(hex) => {
+            let hex = hex.strip_prefix('#').unwrap_or(hex);
+            if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(bad_color());
+            }
+            let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap() as f32 / 255.0;
+            Ok(Color::new(channel(0), channel(2), channel(4)))
+        }
+        Yaml::Array(channels) if channels.len() == 3 => {
+            let channels = channels
+                .iter()
+                .map(|channel| parse_float(channel).map(|f| f as f32))
+                .collect::<Result<Vec<f32>, ParsingError>>()
+                .map_err(|_| bad_color())?;
+            if channels
+                .iter()
+                .any(|channel| !(0.0..=1.0).contains(channel))
+            {
+                return Err(bad_color());
+            }
+            Ok(Color::new(channels[0], channels[1], channels[2]))
+        }
+        _ => Err(bad_color()),
+    }
+}
+
 #[cfg(test)]
 mod parser_tests {
     use super::super::color_scheme::Color;
@@ -413,11 +1529,35 @@ mod parser_tests {
     }
 
     #[test]
-    fn test_parse_size() {
-        let parse =
-            |s: &str| -> Result<Size, ParsingError> { parse_size(&Yaml::String(s.to_string())) };
+    fn test_parse_threads() {
+        assert_eq!(Ok(1), parse_threads(&Yaml::Integer(1)));
+        assert_eq!(Ok(12), parse_threads(&Yaml::Integer(12)));
+    }
 
-        assert_eq!(
+    #[test]
+    fn test_parse_threads_default() {
+        let threads = parse_threads(&Yaml::BadValue).unwrap();
+        assert!(threads >= 1);
+    }
+
+    #[test]
+    fn test_parse_threads_invalid() {
+        assert!(matches!(
+            parse_threads(&Yaml::Integer(0)),
+            Err(ParsingError::BadInteger(_))
+        ));
+        assert!(matches!(
+            parse_threads(&Yaml::String("many".to_string())),
+            Err(ParsingError::BadInteger(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_size() {
+        let parse =
+            |s: &str| -> Result<Size, ParsingError> { parse_size(&Yaml::String(s.to_string())) };
+
+        assert_eq!(
             Ok(Size {
                 width: 100,
                 height: 333
@@ -502,7 +1642,10 @@ mod parser_tests {
             Color::new(0.6875, 1.0, 0.6875),
             cs.color(Iteration::Outside {
                 iterations: 432,
-                max_iterations: 512
+                max_iterations: 512,
+                final_z: Complex::new(2.0, 0.0),
+                escape_length: 2.0,
+                degree: 2.0,
             })
         );
     }
@@ -529,15 +1672,188 @@ mod parser_tests {
         let color0 = cs.color(Iteration::Outside {
             iterations: 0,
             max_iterations: 4096,
+            final_z: Complex::new(2.0, 0.0),
+            escape_length: 2.0,
+            degree: 2.0,
         });
         let color2048 = cs.color(Iteration::Outside {
             iterations: 2048,
             max_iterations: 4096,
+            final_z: Complex::new(2.0, 0.0),
+            escape_length: 2.0,
+            degree: 2.0,
         });
         // Colors should wrap around - same iteration % 2048 should give same color
         assert_eq!(color0, color2048);
     }
 
+    #[test]
+    fn test_parse_color_scheme_smooth() {
+        let input = r#"
+        color_scheme:
+          type: Smooth
+          gradient:
+            - [0.0, 0.0, 0.0]
+            - [1, 0.5, 0.0]
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let cs = parse_color_scheme(&docs[0]["color_scheme"]).unwrap();
+        assert_eq!(
+            Color::new(1.0, 0.5, 0.0),
+            cs.color(Iteration::Outside {
+                iterations: 100,
+                max_iterations: 100,
+                final_z: Complex::new(2.0, 0.0),
+                escape_length: 2.0,
+                degree: 2.0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_color_scheme_smooth_default_gradient() {
+        let input = r#"
+        color_scheme:
+          type: Smooth
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        assert!(parse_color_scheme(&docs[0]["color_scheme"]).is_ok());
+    }
+
+    #[test]
+    fn test_parse_gradient() {
+        let input = r#"
+        gradient:
+          - [0.0, 0.25, 1.0]
+          - [1, 1, 1]
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        assert_eq!(
+            Ok(Gradient::new(vec![
+                Color::new(0.0, 0.25, 1.0),
+                Color::new(1.0, 1.0, 1.0)
+            ])),
+            parse_gradient(&docs[0]["gradient"])
+        );
+    }
+
+    #[test]
+    fn test_parse_gradient_errors() {
+        let parse = |input: &str| {
+            let docs = YamlLoader::load_from_str(input).unwrap();
+            parse_gradient(&docs[0]["gradient"])
+        };
+        assert!(matches!(
+            parse("gradient: []"),
+            Err(ParsingError::BadGradient(_))
+        ));
+        assert!(matches!(
+            parse("gradient: red"),
+            Err(ParsingError::BadGradient(_))
+        ));
+        assert!(matches!(
+            parse("gradient: [[0.0, 1.0]]"),
+            Err(ParsingError::BadGradient(_))
+        ));
+        assert!(matches!(
+            parse("gradient: [[0.0, 1.0, blue]]"),
+            Err(ParsingError::BadGradient(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_color_scheme_gradient() {
+        let input = r#"
+        color_scheme:
+          type: Gradient
+          stops:
+            - position: 0.0
+              color: '#000000'
+            - position: 1
+              color: [1.0, 1.0, 1.0]
+          mode: mirror
+          offset: 0.5
+          density: 0.125
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let cs = parse_color_scheme(&docs[0]["color_scheme"]).unwrap();
+        let outside = |iterations| Iteration::Outside {
+            iterations,
+            max_iterations: 512,
+            final_z: Complex::new(2.0, 0.0),
+            escape_length: 2.0,
+            degree: 2.0,
+        };
+        assert_eq!(Color::new(0.75, 0.75, 0.75), cs.color(outside(2)));
+        assert_eq!(Color::new(0.75, 0.75, 0.75), cs.color(outside(6)));
+    }
+
+    #[test]
+    fn test_parse_color_scheme_gradient_defaults() {
+        let input = r#"
+        color_scheme:
+          type: Gradient
+          stops:
+            - position: 0.0
+              color: '#000000'
+            - position: 1.0
+              color: '#ffffff'
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let cs = parse_color_scheme(&docs[0]["color_scheme"]).unwrap();
+        assert_eq!(
+            Color::new(0.5, 0.5, 0.5),
+            cs.color(Iteration::Outside {
+                iterations: 256,
+                max_iterations: 512,
+                final_z: Complex::new(2.0, 0.0),
+                escape_length: 2.0,
+                degree: 2.0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_color_scheme_gradient_errors() {
+        let parse = |scheme: &str| {
+            let docs = YamlLoader::load_from_str(scheme).unwrap();
+            parse_color_scheme(&docs[0]["color_scheme"])
+        };
+        for scheme in [
+            "color_scheme: { type: Gradient }",
+            "color_scheme: { type: Gradient, stops: [] }",
+            "color_scheme: { type: Gradient, stops: [{ color: '#000000' }] }",
+            "color_scheme: { type: Gradient, stops: [{ position: 1.5, color: '#000000' }] }",
+            "color_scheme: { type: Gradient, stops: [{ position: 0.5, color: '#0000' }] }",
+            "color_scheme: { type: Gradient, stops: [{ position: 0.5, color: '#00000g' }] }",
+            "color_scheme: { type: Gradient, stops: [{ position: 0.5, color: [2, 0, 0] }] }",
+            r#"
+        color_scheme:
+          type: Gradient
+          stops:
+            - { position: 0.75, color: '#000000' }
+            - { position: 0.25, color: '#ffffff' }"#,
+            "color_scheme: { type: Gradient, mode: wrap, stops: [{ position: 0, color: '#000000' }] }",
+        ] {
+            assert!(
+                matches!(parse(scheme), Err(ParsingError::BadGradient(_))),
+                "expected a gradient error from {}",
+                scheme
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_color() {
+        let parse = |input: &str| {
+            let docs = YamlLoader::load_from_str(input).unwrap();
+            parse_color(&docs[0]["color"])
+        };
+        assert_eq!(Ok(Color::new(1.0, 0.0, 0.2)), parse("color: '#FF0033'"));
+        assert_eq!(Ok(Color::new(1.0, 0.0, 0.2)), parse("color: 'ff0033'"));
+        assert_eq!(Ok(Color::new(0.5, 0.0, 1.0)), parse("color: [0.5, 0, 1]"));
+    }
+
     #[test]
     fn test_parse_fractal() {
         let input = r#"
@@ -550,9 +1866,9 @@ mod parser_tests {
         let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
 
         // Downcast to Julia to verify the values
-        let julia = fractal.as_any().downcast_ref::<Julia>().unwrap();
+        let julia = fractal.as_any().downcast_ref::<Mandelbrot>().unwrap();
         assert_eq!(julia.max_iterations, 376);
-        assert_eq!(julia.c, Complex::new(1.0, 2.0));
+        assert_eq!(julia.plane, Plane::Dynamical(Complex::new(1.0, 2.0)));
 
         // test default max_iterations
         let input = r#"
@@ -563,9 +1879,9 @@ mod parser_tests {
         let docs = YamlLoader::load_from_str(input).unwrap();
         let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
 
-        let julia = fractal.as_any().downcast_ref::<Julia>().unwrap();
+        let julia = fractal.as_any().downcast_ref::<Mandelbrot>().unwrap();
         assert_eq!(julia.max_iterations, 128);
-        assert_eq!(julia.c, Complex::new(1.0, 2.0));
+        assert_eq!(julia.plane, Plane::Dynamical(Complex::new(1.0, 2.0)));
     }
 
     // Error handling tests
@@ -660,7 +1976,7 @@ mod parser_tests {
 
         let burning_ship = fractal.as_any().downcast_ref::<BurningShip>().unwrap();
         assert_eq!(burning_ship.max_iterations, 128);
-        assert_eq!(burning_ship.escape_length, 2.0);
+        assert_eq!(burning_ship.bailout, Bailout::Circle(2.0));
     }
 
     #[test]
@@ -676,104 +1992,1650 @@ mod parser_tests {
 
         let burning_ship = fractal.as_any().downcast_ref::<BurningShip>().unwrap();
         assert_eq!(burning_ship.max_iterations, 1024);
-        assert_eq!(burning_ship.escape_length, 4.0);
+        assert_eq!(burning_ship.bailout, Bailout::Circle(4.0));
     }
 
     #[test]
-    fn test_parse_complex_invalid_format() {
-        let result = parse_complex(&Yaml::String("invalid".to_string()));
+    fn test_parse_fractal_tricorn() {
+        let input = r#"
+        fractal:
+          type: Tricorn
+          max_iterations: 300
+          escapeLength: 3.0
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let tricorn = fractal.as_any().downcast_ref::<Tricorn>().unwrap();
+        assert_eq!(tricorn.max_iterations, 300);
+        assert_eq!(tricorn.bailout, Bailout::Circle(3.0));
+    }
+
+    #[test]
+    fn test_parse_fractal_tricorn_julia() {
+        let input = r#"
+        fractal:
+          type: TricornJulia
+          c: 0.3+0.52i
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let tricorn_julia = fractal.as_any().downcast_ref::<Tricorn>().unwrap();
+        assert_eq!(tricorn_julia.max_iterations, 128);
         assert_eq!(
-            result,
-            Err(ParsingError::BadComplexNumber("invalid".to_string()))
+            tricorn_julia.plane,
+            Plane::Dynamical(Complex::new(0.3, 0.52))
         );
+        assert_eq!(tricorn_julia.bailout, Bailout::Circle(2.0));
     }
 
     #[test]
-    fn test_parse_complex_not_a_string() {
-        let result = parse_complex(&Yaml::Integer(42));
+    fn test_parse_fractal_tricorn_julia_missing_c() {
+        let input = r#"
+        fractal:
+          type: TricornJulia
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let result = parse_fractal(&docs[0]["fractal"]);
         assert!(matches!(result, Err(ParsingError::BadComplexNumber(_))));
     }
 
     #[test]
-    fn test_parse_size_invalid_format() {
-        let result = parse_size(&Yaml::String("100".to_string()));
-        assert!(matches!(result, Err(ParsingError::BadSize(_))));
+    fn test_parse_fractal_phoenix() {
+        let input = r#"
+        fractal:
+          type: Phoenix
+          p: -0.5+0.0i
+          max_iterations: 256
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let phoenix = fractal.as_any().downcast_ref::<Phoenix>().unwrap();
+        assert_eq!(phoenix.max_iterations, 256);
+        assert_eq!(phoenix.p, Complex::new(-0.5, 0.0));
+        assert_eq!(phoenix.bailout, Bailout::Circle(2.0));
     }
 
     #[test]
-    fn test_parse_size_invalid_numbers() {
-        let result = parse_size(&Yaml::String("100xabc".to_string()));
-        assert!(matches!(result, Err(ParsingError::BadSize(_))));
+    fn test_parse_fractal_phoenix_julia() {
+        let input = r#"
+        fractal:
+          type: PhoenixJulia
+          c: 0.5667+0.0i
+          p: -0.5+0.0i
+          escapeLength: 4
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let phoenix_julia = fractal.as_any().downcast_ref::<Phoenix>().unwrap();
+        assert_eq!(phoenix_julia.max_iterations, 128);
+        assert_eq!(
+            phoenix_julia.plane,
+            Plane::Dynamical(Complex::new(0.5667, 0.0))
+        );
+        assert_eq!(phoenix_julia.p, Complex::new(-0.5, 0.0));
+        assert_eq!(phoenix_julia.bailout, Bailout::Circle(4.0));
     }
 
     #[test]
-    fn test_parse_size_too_many_dimensions() {
-        let result = parse_size(&Yaml::String("100x200x300".to_string()));
-        assert!(matches!(result, Err(ParsingError::BadSize(_))));
+    fn test_parse_fractal_phoenix_missing_p() {
+        let input = r#"
+        fractal:
+          type: PhoenixJulia
+          c: 0.5667+0.0i
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let result = parse_fractal(&docs[0]["fractal"]);
+        assert!(matches!(result, Err(ParsingError::BadComplexNumber(_))));
     }
 
     #[test]
-    fn test_parse_color_scheme_missing_type() {
+    fn test_parse_fractal_newton_roots() {
         let input = r#"
-        color_scheme:
-          foo: bar
+        fractal:
+          type: Newton
+          max_iterations: 50
+          roots: [1, -0.5+0.866i, -0.5-0.866i]
       "#;
         let docs = YamlLoader::load_from_str(input).unwrap();
-        let result = parse_color_scheme(&docs[0]["color_scheme"]);
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let newton = fractal.as_any().downcast_ref::<Newton>().unwrap();
+        assert_eq!(newton.max_iterations, 50);
+        assert_eq!(newton.epsilon, 1e-6);
+        assert_eq!(
+            newton.roots,
+            vec![
+                Complex::new(1.0, 0.0),
+                Complex::new(-0.5, 0.866),
+                Complex::new(-0.5, -0.866)
+            ]
+        );
+        assert_eq!(newton.polynomial.degree(), 3);
+    }
+
+    #[test]
+    fn test_parse_fractal_newton_coefficients() {
+        let input = r#"
+        fractal:
+          type: Newton
+          coefficients: [1, 0, -4]
+          epsilon: 0.001
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let newton = fractal.as_any().downcast_ref::<Newton>().unwrap();
+        assert_eq!(newton.max_iterations, 128);
+        assert_eq!(newton.epsilon, 0.001);
+        assert_eq!(newton.polynomial.degree(), 2);
+        let mut roots: Vec<f64> = newton.roots.iter().map(|root| root.re).collect();
+        roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!((roots[0] + 2.0).abs() < 1e-12);
+        assert!((roots[1] - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_parse_fractal_newton_errors() {
+        let parse = |input: &str| {
+            let docs = YamlLoader::load_from_str(input).unwrap();
+            parse_fractal(&docs[0]["fractal"])
+        };
         assert!(matches!(
-            result,
-            Err(ParsingError::MissingField(ref msg)) if msg == "color_scheme type"
+            parse("fractal: { type: Newton }"),
+            Err(ParsingError::MissingField(_))
+        ));
+        assert!(matches!(
+            parse("fractal: { type: Newton, roots: [1], coefficients: [1, -1] }"),
+            Err(ParsingError::BadFractal(_))
+        ));
+        assert!(matches!(
+            parse("fractal: { type: Newton, coefficients: [0, 5] }"),
+            Err(ParsingError::BadFractal(_))
+        ));
+        assert!(matches!(
+            parse("fractal: { type: Newton, roots: [] }"),
+            Err(ParsingError::BadFractal(_))
+        ));
+        assert!(matches!(
+            parse("fractal: { type: Newton, roots: [one] }"),
+            Err(ParsingError::BadComplexNumber(_))
+        ));
+        assert!(matches!(
+            parse("fractal: { type: Newton, roots: [1, -1], epsilon: 0 }"),
+            Err(ParsingError::BadFloat(_))
         ));
     }
 
     #[test]
-    fn test_parse_color_scheme_invalid_type() {
+    fn test_parse_renderer_escape_time() {
         let input = r#"
+        fractal:
+          type: Mandelbrot
         color_scheme:
-          type: InvalidScheme
+          type: Gray
       "#;
         let docs = YamlLoader::load_from_str(input).unwrap();
-        let result = parse_color_scheme(&docs[0]["color_scheme"]);
-        assert!(matches!(result, Err(ParsingError::BadColorScheme(_))));
+        let renderer = parse_renderer(&docs[0]).unwrap();
+
+        let renderer = renderer
+            .as_any()
+            .downcast_ref::<EscapeTimeRenderer>()
+            .unwrap();
+        assert!(renderer.fractal.as_any().is::<Mandelbrot>());
     }
 
     #[test]
-    fn test_parse_image_missing_upper_left() {
+    fn test_parse_renderer_buddhabrot() {
         let input = r#"
-      image:
-        size: 512x384
-        lowerRight: 1.2+-1.2i
-    "#;
+        fractal:
+          type: Buddhabrot
+      "#;
         let docs = YamlLoader::load_from_str(input).unwrap();
-        let result = parse_image(&String::from("data/foobar.yml"), &docs[0]["image"]);
-        assert!(matches!(result, Err(ParsingError::BadComplexNumber(_))));
+        let renderer = parse_renderer(&docs[0]).unwrap();
+
+        let buddhabrot = renderer.as_any().downcast_ref::<Buddhabrot>().unwrap();
+        assert_eq!(buddhabrot.max_iterations, [1000, 1000, 1000]);
+        assert_eq!(buddhabrot.plane, Plane::Parameter);
+        assert_eq!(buddhabrot.bailout, Bailout::Circle(2.0));
+        assert_eq!(buddhabrot.steps.len(), 1);
+        assert_eq!(buddhabrot.samples, 1_000_000);
+        assert_eq!(buddhabrot.sample_radius, 2.0);
+        assert_eq!(buddhabrot.seed, 0);
+        assert_eq!(buddhabrot.tone_map, ToneMap::Sqrt);
     }
 
     #[test]
-    fn test_parse_image_missing_lower_right() {
+    fn test_parse_renderer_nebulabrot() {
         let input = r#"
-      image:
-        size: 512x384
-        upperLeft: -2.0+1.2i
-    "#;
+        fractal:
+          type: Nebulabrot
+          formulas: [Tricorn]
+          samples: 5000
+          sampleRadius: 2.5
+          seed: 42
+          tone: log
+      "#;
         let docs = YamlLoader::load_from_str(input).unwrap();
-        let result = parse_image(&String::from("data/foobar.yml"), &docs[0]["image"]);
-        assert!(matches!(result, Err(ParsingError::BadComplexNumber(_))));
+        let renderer = parse_renderer(&docs[0]).unwrap();
+
+        let nebulabrot = renderer.as_any().downcast_ref::<Buddhabrot>().unwrap();
+        assert_eq!(nebulabrot.max_iterations, [5000, 500, 50]);
+        assert_eq!(nebulabrot.steps.len(), 1);
+        assert_eq!(nebulabrot.samples, 5000);
+        assert_eq!(nebulabrot.sample_radius, 2.5);
+        assert_eq!(nebulabrot.seed, 42);
+        assert_eq!(nebulabrot.tone_map, ToneMap::Log);
+
+        let input = r#"
+        fractal:
+          type: Nebulabrot
+          max_iterations: [2000, 200, 20]
+          plane: dynamical
+          c: -0.4+0.6i
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let renderer = parse_renderer(&docs[0]).unwrap();
+
+        let nebulabrot = renderer.as_any().downcast_ref::<Buddhabrot>().unwrap();
+        assert_eq!(nebulabrot.max_iterations, [2000, 200, 20]);
+        assert_eq!(nebulabrot.plane, Plane::Dynamical(Complex::new(-0.4, 0.6)));
     }
 
     #[test]
-    fn test_parse_u64_string_value() {
-        let result = parse_u64(&Yaml::String("not a number".to_string()));
+    fn test_parse_renderer_buddhabrot_errors() {
+        for (fractal, expected) in [
+            (
+                "{type: Nebulabrot, max_iterations: [100, 10]}",
+                ParsingError::BadInteger("Array([Integer(100), Integer(10)]) is not an iteration limit or a list of three".to_string()),
+            ),
+            (
+                "{type: Nebulabrot, max_iterations: [0, -5, 100]}",
+                ParsingError::BadInteger(
+                    "iteration limits must be positive, got [0, -5, 100]".to_string(),
+                ),
+            ),
+            (
+                "{type: Buddhabrot, max_iterations: 0}",
+                ParsingError::BadInteger("iteration limits must be positive, got [0, 0, 0]".to_string()),
+            ),
+            (
+                "{type: Buddhabrot, samples: -1}",
+                ParsingError::BadInteger("Integer(-1)".to_string()),
+            ),
+            (
+                "{type: Buddhabrot, sampleRadius: 0}",
+                ParsingError::BadFloat("sampleRadius must be positive, got 0".to_string()),
+            ),
+            (
+                "{type: Buddhabrot, tone: bright}",
+                ParsingError::BadFractal(
+                    "String(\"bright\") is not a valid tone (linear, sqrt or log)".to_string(),
+                ),
+            ),
+            (
+                "{type: Buddhabrot, formulas: [Newton]}",
+                ParsingError::BadFractal("Newton can't be part of a Hybrid (Mandelbrot, Tricorn, Transcendental or a Burning Ship)".to_string()),
+            ),
+        ] {
+            let docs = YamlLoader::load_from_str(&format!("fractal: {}", fractal)).unwrap();
+            assert_eq!(expected, parse_renderer(&docs[0]).unwrap_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_renderer_ifs_presets() {
+        let input = r#"
+        fractal:
+          type: BarnsleyFern
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let renderer = parse_renderer(&docs[0]).unwrap();
+
+        let fern = renderer.as_any().downcast_ref::<Ifs>().unwrap();
+        assert_eq!(fern.transforms, ifs::barnsley_fern());
+        assert_eq!(fern.points, 1_000_000);
+        assert_eq!(fern.seed, 0);
+        assert_eq!(fern.tone_map, ToneMap::Sqrt);
+
+        let input = r#"
+        fractal:
+          type: Sierpinski
+          points: 5000
+          seed: 9
+          tone: linear
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let renderer = parse_renderer(&docs[0]).unwrap();
+
+        let triangle = renderer.as_any().downcast_ref::<Ifs>().unwrap();
+        assert_eq!(triangle.transforms, ifs::sierpinski());
+        assert_eq!(triangle.points, 5000);
+        assert_eq!(triangle.seed, 9);
+        assert_eq!(triangle.tone_map, ToneMap::Linear);
+    }
+
+    #[test]
+    fn test_parse_renderer_ifs_transforms() {
+        let input = r#"
+        fractal:
+          type: IFS
+          transforms:
+            - {a: 0.5, d: 0.5, probability: 0.25}
+            - {a: 0.5, b: -0.1, c: 0.1, d: 0.5, e: 0.5, f: 1}
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let renderer = parse_renderer(&docs[0]).unwrap();
+
+        let ifs = renderer.as_any().downcast_ref::<Ifs>().unwrap();
         assert_eq!(
-            result,
-            Err(ParsingError::BadInteger("not a number".to_string()))
+            ifs.transforms,
+            vec![
+                Transform::new(0.5, 0.0, 0.0, 0.5, 0.0, 0.0, 0.25),
+                Transform::new(0.5, -0.1, 0.1, 0.5, 0.5, 1.0, 1.0),
+            ]
         );
     }
 
     #[test]
-    fn test_parse_nonexistent_file() {
-        let result = parse(&String::from("/nonexistent/file.yml"));
-        assert!(matches!(result, Err(ParsingError::IoError(_))));
+    fn test_parse_renderer_ifs_errors() {
+        for (fractal, expected) in [
+            (
+                "{type: IFS}",
+                ParsingError::MissingField("transforms".to_string()),
+            ),
+            (
+                "{type: IFS, transforms: []}",
+                ParsingError::BadFractal("Array([]) is not a list of transforms".to_string()),
+            ),
+            (
+                "{type: IFS, transforms: [0.5]}",
+                ParsingError::BadFractal("Real(\"0.5\") is not a transform".to_string()),
+            ),
+            (
+                "{type: IFS, transforms: [{a: x}]}",
+                ParsingError::BadFloat("String(\"x\")".to_string()),
+            ),
+            (
+                "{type: IFS, transforms: [{probability: -1}]}",
+                ParsingError::BadFloat(
+                    "probability must be a non-negative number, got -1".to_string(),
+                ),
+            ),
+            (
+                "{type: Sierpinski, transforms: [{probability: 0}]}",
+                ParsingError::BadFractal(
+                    "at least one transform needs a positive probability".to_string(),
+                ),
+            ),
+        ] {
+            let docs = YamlLoader::load_from_str(&format!("fractal: {}", fractal)).unwrap();
+            assert_eq!(expected, parse_renderer(&docs[0]).unwrap_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_renderer_flame() {
+        let input = r#"
+        fractal:
+          type: Flame
+          transforms:
+            - {a: 0.5, d: 0.5, variations: {swirl: 0.75, linear: 0.25}}
+            - {e: 0.5, probability: 3, color: 0.1}
+            - {f: 0.5}
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let renderer = parse_renderer(&docs[0]).unwrap();
+
+        let flame = renderer.as_any().downcast_ref::<Flame>().unwrap();
+        assert_eq!(
+            flame.transforms,
+            vec![
+                FlameTransform {
+                    affine: Affine {
+                        a: 0.5,
+                        d: 0.5,
+                        ..Affine::default()
+                    },
+                    probability: 1.0,
+                    variations: vec![(Variation::Swirl, 0.75), (Variation::Linear, 0.25)],
+                    color: 0.0,
+                },
+                FlameTransform {
+                    affine: Affine {
+                        e: 0.5,
+                        ..Affine::default()
+                    },
+                    probability: 3.0,
+                    variations: vec![(Variation::Linear, 1.0)],
+                    color: 0.1,
+                },
+                FlameTransform {
+                    affine: Affine {
+                        f: 0.5,
+                        ..Affine::default()
+                    },
+                    probability: 1.0,
+                    variations: vec![(Variation::Linear, 1.0)],
+                    color: 1.0,
+                },
+            ]
+        );
+        assert_eq!(flame.palette, Gradient::default());
+        assert_eq!(flame.points, 1_000_000);
+        assert_eq!(flame.seed, 0);
+        assert_eq!(flame.gamma, 4.0);
+        assert_eq!(flame.vibrancy, 1.0);
+
+        let input = r#"
+        fractal:
+          type: Flame
+          transforms: [{}]
+          palette: ['#000000', '#ffffff']
+          points: 200
+          seed: 3
+          gamma: 2.2
+          vibrancy: 0.5
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let renderer = parse_renderer(&docs[0]).unwrap();
+
+        let flame = renderer.as_any().downcast_ref::<Flame>().unwrap();
+        assert_eq!(flame.transforms[0].color, 0.0);
+        assert_eq!(
+            flame.palette,
+            Gradient::new(vec![Color::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0)])
+        );
+        assert_eq!(flame.points, 200);
+        assert_eq!(flame.seed, 3);
+        assert_eq!(flame.gamma, 2.2);
+        assert_eq!(flame.vibrancy, 0.5);
+    }
+
+    #[test]
+    fn test_parse_renderer_flame_errors() {
+        for (fractal, expected) in [
+            (
+                "{type: Flame}",
+                ParsingError::MissingField("transforms".to_string()),
+            ),
+            (
+                "{type: Flame, transforms: [{variations: {twirl: 1}}]}",
+                ParsingError::BadFractal("String(\"twirl\") is not a valid variation (linear, sinusoidal, spherical, swirl, horseshoe, polar, handkerchief, heart, disc, spiral, hyperbolic or diamond)".to_string()),
+            ),
+            (
+                "{type: Flame, transforms: [{variations: [swirl]}]}",
+                ParsingError::BadFractal("Array([String(\"swirl\")]) is not a mapping of variations to weights".to_string()),
+            ),
+            (
+                "{type: Flame, transforms: [{color: 1.5}]}",
+                ParsingError::BadFloat("color 1.5 is not between 0.0 and 1.0".to_string()),
+            ),
+            (
+                "{type: Flame, transforms: [{}], gamma: 0}",
+                ParsingError::BadFloat("gamma must be positive, got 0".to_string()),
+            ),
+            (
+                "{type: Flame, transforms: [{}], vibrancy: -0.5}",
+                ParsingError::BadFloat("vibrancy must be between 0.0 and 1.0, got -0.5".to_string()),
+            ),
+            (
+                "{type: Flame, transforms: [{}], vibrancy: 1.5}",
+                ParsingError::BadFloat("vibrancy must be between 0.0 and 1.0, got 1.5".to_string()),
+            ),
+            (
+                "{type: Flame, transforms: [{probability: 0}]}",
+                ParsingError::BadFractal(
+                    "at least one transform needs a positive probability".to_string(),
+                ),
+            ),
+        ] {
+            let docs = YamlLoader::load_from_str(&format!("fractal: {}", fractal)).unwrap();
+            assert_eq!(expected, parse_renderer(&docs[0]).unwrap_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_renderer_attractors() {
+        let input = r#"
+        fractal:
+          type: Clifford
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let renderer = parse_renderer(&docs[0]).unwrap();
+
+        let clifford = renderer
+            .as_any()
+            .downcast_ref::<StrangeAttractor>()
+            .unwrap();
+        assert_eq!(clifford.map, Map::Clifford);
+        assert_eq!(
+            (clifford.a, clifford.b, clifford.c, clifford.d),
+            (-1.4, 1.6, 1.0, 0.7)
+        );
+        assert_eq!(clifford.points, 1_000_000);
+        assert_eq!(clifford.seed, 0);
+        assert_eq!(
+            clifford.gradient,
+            Gradient::new(vec![Color::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0)])
+        );
+        assert_eq!(clifford.tone_map, ToneMap::Log);
+
+        let input = r#"
+        fractal:
+          type: DeJong
+          a: -2.0
+          b: -2
+          c: -1.2
+          d: 2.0
+          points: 20000
+          seed: 4
+          gradient: ['#ffffff', '#000080']
+          tone: sqrt
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let renderer = parse_renderer(&docs[0]).unwrap();
+
+        let de_jong = renderer
+            .as_any()
+            .downcast_ref::<StrangeAttractor>()
+            .unwrap();
+        assert_eq!(de_jong.map, Map::DeJong);
+        assert_eq!(
+            (de_jong.a, de_jong.b, de_jong.c, de_jong.d),
+            (-2.0, -2.0, -1.2, 2.0)
+        );
+        assert_eq!(de_jong.points, 20000);
+        assert_eq!(de_jong.seed, 4);
+        assert_eq!(
+            de_jong.gradient,
+            Gradient::new(vec![
+                Color::new(1.0, 1.0, 1.0),
+                Color::new(0.0, 0.0, 128.0 / 255.0)
+            ])
+        );
+        assert_eq!(de_jong.tone_map, ToneMap::Sqrt);
+
+        let input = r#"
+        fractal:
+          type: Henon
+          b: 0.2
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let renderer = parse_renderer(&docs[0]).unwrap();
+
+        let henon = renderer
+            .as_any()
+            .downcast_ref::<StrangeAttractor>()
+            .unwrap();
+        assert_eq!(henon.map, Map::Henon);
+        assert_eq!((henon.a, henon.b), (1.4, 0.2));
+    }
+
+    #[test]
+    fn test_parse_renderer_attractor_errors() {
+        let docs = YamlLoader::load_from_str("fractal: {type: Clifford, a: big}").unwrap();
+        assert_eq!(
+            ParsingError::BadFloat("String(\"big\")".to_string()),
+            parse_renderer(&docs[0]).unwrap_err()
+        );
+        let docs = YamlLoader::load_from_str("fractal: {type: Henon, gradient: []}").unwrap();
+        assert_eq!(
+            ParsingError::BadGradient(
+                "gradient must be a non-empty list of colors, got Array([])".to_string()
+            ),
+            parse_renderer(&docs[0]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_renderer_bifurcation() {
+        let input = r#"
+        fractal:
+          type: Bifurcation
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let renderer = parse_renderer(&docs[0]).unwrap();
+
+        let logistic = renderer.as_any().downcast_ref::<Bifurcation>().unwrap();
+        assert_eq!(logistic.map, RealMap::Logistic);
+        assert_eq!(logistic.x0, 0.5);
+        assert_eq!(logistic.transient, 1000);
+        assert_eq!(logistic.points, 1000);
+        assert_eq!(logistic.samples, 4);
+        assert_eq!(
+            logistic.gradient,
+            Gradient::new(vec![Color::new(1.0, 1.0, 1.0), Color::new(0.0, 0.0, 0.0)])
+        );
+        assert_eq!(logistic.tone_map, ToneMap::Log);
+
+        let input = r#"
+        fractal:
+          type: Bifurcation
+          map: quadratic
+          transient: 500
+          points: 2000
+          samples: 1
+          gradient: ['#000000', '#ffffff']
+          tone: sqrt
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let renderer = parse_renderer(&docs[0]).unwrap();
+
+        let quadratic = renderer.as_any().downcast_ref::<Bifurcation>().unwrap();
+        assert_eq!(quadratic.map, RealMap::Quadratic);
+        assert_eq!(quadratic.x0, 0.0);
+        assert_eq!(quadratic.transient, 500);
+        assert_eq!(quadratic.points, 2000);
+        assert_eq!(quadratic.samples, 1);
+        assert_eq!(
+            quadratic.gradient,
+            Gradient::new(vec![Color::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0)])
+        );
+        assert_eq!(quadratic.tone_map, ToneMap::Sqrt);
+
+        let docs =
+            YamlLoader::load_from_str("fractal: {type: Bifurcation, map: tent, x0: 0.3}").unwrap();
+        let renderer = parse_renderer(&docs[0]).unwrap();
+        let tent = renderer.as_any().downcast_ref::<Bifurcation>().unwrap();
+        assert_eq!((tent.map, tent.x0), (RealMap::Tent, 0.3));
+    }
+
+    #[test]
+    fn test_parse_renderer_bifurcation_errors() {
+        for (input, expected) in [
+            (
+                "fractal: {type: Bifurcation, map: cubic}",
+                ParsingError::BadFractal(
+                    "String(\"cubic\") is not a valid map (logistic, sine, tent or quadratic)"
+                        .to_string(),
+                ),
+            ),
+            (
+                "fractal: {type: Bifurcation, samples: 0}",
+                ParsingError::BadInteger("samples must be positive".to_string()),
+            ),
+            (
+                "fractal: {type: Bifurcation, points: -5}",
+                ParsingError::BadInteger("Integer(-5)".to_string()),
+            ),
+        ] {
+            let docs = YamlLoader::load_from_str(input).unwrap();
+            assert_eq!(expected, parse_renderer(&docs[0]).unwrap_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_renderer_lyapunov() {
+        let input = r#"
+        fractal:
+          type: Lyapunov
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let renderer = parse_renderer(&docs[0]).unwrap();
+
+        let lyapunov = renderer
+            .as_any()
+            .downcast_ref::<LyapunovRenderer>()
+            .unwrap();
+        assert_eq!(
+            lyapunov.fractal,
+            Lyapunov {
+                sequence: vec![Rate::A, Rate::B],
+                warmup: 200,
+                iterations: 1000,
+                x0: 0.5,
+            }
+        );
+        assert_eq!(lyapunov.color_scheme, LyapunovColors::default());
+
+        let input = r#"
+        fractal:
+          type: Lyapunov
+          sequence: AABAB
+          warmup: 50
+          iterations: 400
+          x0: 0.25
+        color_scheme:
+          type: Lyapunov
+          chaotic: ['#ffffff', '#000080']
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let renderer = parse_renderer(&docs[0]).unwrap();
+
+        let lyapunov = renderer
+            .as_any()
+            .downcast_ref::<LyapunovRenderer>()
+            .unwrap();
+        assert_eq!(
+            lyapunov.fractal,
+            Lyapunov {
+                sequence: vec![Rate::A, Rate::A, Rate::B, Rate::A, Rate::B],
+                warmup: 50,
+                iterations: 400,
+                x0: 0.25,
+            }
+        );
+        assert_eq!(
+            lyapunov.color_scheme.stable,
+            LyapunovColors::default().stable
+        );
+        assert_eq!(
+            lyapunov.color_scheme.chaotic,
+            Gradient::new(vec![
+                Color::new(1.0, 1.0, 1.0),
+                Color::new(0.0, 0.0, 128.0 / 255.0)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_renderer_lyapunov_errors() {
+        for (input, expected) in [
+            (
+                "fractal: {type: Lyapunov, sequence: ABC}",
+                ParsingError::BadFractal("\"ABC\" is not a sequence of A and B".to_string()),
+            ),
+            (
+                "fractal: {type: Lyapunov, sequence: \"\"}",
+                ParsingError::BadFractal("\"\" is not a sequence of A and B".to_string()),
+            ),
+            (
+                "fractal: {type: Lyapunov, sequence: 12}",
+                ParsingError::BadFractal("Integer(12) is not a sequence of A and B".to_string()),
+            ),
+            (
+                "fractal: {type: Lyapunov, warmup: 0}",
+                ParsingError::BadInteger("warmup must be positive when x0 is 0.5".to_string()),
+            ),
+            (
+                "fractal: {type: Lyapunov, iterations: 0}",
+                ParsingError::BadInteger("iterations must be positive".to_string()),
+            ),
+            (
+                "{fractal: {type: Lyapunov}, color_scheme: {type: Smooth}}",
+                ParsingError::BadColorScheme(
+                    "Smooth is not a color scheme for Lyapunov fractals".to_string(),
+                ),
+            ),
+        ] {
+            let docs = YamlLoader::load_from_str(input).unwrap();
+            assert_eq!(expected, parse_renderer(&docs[0]).unwrap_err());
+        }
+
+        let docs = YamlLoader::load_from_str("fractal: {type: Lyapunov, warmup: 0, x0: 0.25}");
+        assert!(parse_renderer(&docs.unwrap()[0]).is_ok());
+    }
+
+    #[test]
+    fn test_parse_fractal_collatz() {
+        let input = r#"
+        fractal:
+          type: Collatz
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let collatz = fractal.as_any().downcast_ref::<Collatz>().unwrap();
+        assert_eq!(collatz.bailout, Bailout::Circle(1e10));
+
+        let input = r#"
+        fractal:
+          type: Collatz
+          escapeLength: 1000.0
+          bailout: square
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let collatz = fractal.as_any().downcast_ref::<Collatz>().unwrap();
+        assert_eq!(collatz.bailout, Bailout::Square(1000.0));
+    }
+
+    #[test]
+    fn test_parse_fractal_lambda() {
+        let input = r#"
+        fractal:
+          type: Lambda
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let lambda = fractal.as_any().downcast_ref::<Lambda>().unwrap();
+        assert_eq!(lambda.max_iterations, 128);
+        assert_eq!(lambda.plane, Plane::Parameter);
+        assert_eq!(lambda.bailout, Bailout::Circle(100.0));
+
+        let input = r#"
+        fractal:
+          type: LambdaJulia
+          c: 2.9+0.2i
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let lambda = fractal.as_any().downcast_ref::<Lambda>().unwrap();
+        assert_eq!(lambda.plane, Plane::Dynamical(Complex::new(2.9, 0.2)));
+    }
+
+    #[test]
+    fn test_parse_fractal_magnet1() {
+        let input = r#"
+        fractal:
+          type: Magnet1
+          max_iterations: 256
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let magnet1 = fractal.as_any().downcast_ref::<Magnet1>().unwrap();
+        assert_eq!(magnet1.max_iterations, 256);
+        assert_eq!(magnet1.epsilon, 1e-6);
+        assert_eq!(magnet1.bailout, Bailout::Circle(100.0));
+    }
+
+    #[test]
+    fn test_parse_fractal_magnet2_julia() {
+        let input = r#"
+        fractal:
+          type: Magnet2Julia
+          c: 1.5+0.5i
+          epsilon: 0.001
+          escapeLength: 1000
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let magnet2_julia = fractal.as_any().downcast_ref::<Magnet2>().unwrap();
+        assert_eq!(
+            magnet2_julia.plane,
+            Plane::Dynamical(Complex::new(1.5, 0.5))
+        );
+        assert_eq!(magnet2_julia.epsilon, 0.001);
+        assert_eq!(magnet2_julia.bailout, Bailout::Circle(1000.0));
+    }
+
+    #[test]
+    fn test_parse_fractal_magnet1_julia_missing_c() {
+        let input = r#"
+        fractal:
+          type: Magnet1Julia
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let result = parse_fractal(&docs[0]["fractal"]);
+        assert!(matches!(result, Err(ParsingError::BadComplexNumber(_))));
+    }
+
+    #[test]
+    fn test_parse_fractal_burning_ship_variants() {
+        let variants = [
+            ("BurningShip", Variant::BurningShip),
+            ("Buffalo", Variant::Buffalo),
+            ("Celtic", Variant::Celtic),
+            ("HeartMandelbrot", Variant::Heart),
+            ("PerpendicularBurningShip", Variant::Perpendicular),
+        ];
+        for (name, variant) in variants.iter() {
+            let docs = YamlLoader::load_from_str(&format!("{{type: {}, power: 3}}", name)).unwrap();
+            let fractal = parse_fractal(&docs[0]).unwrap();
+
+            let burning_ship = fractal.as_any().downcast_ref::<BurningShip>().unwrap();
+            assert_eq!(burning_ship.variant, *variant);
+            assert_eq!(burning_ship.power, Power::Integer(3));
+        }
+    }
+
+    #[test]
+    fn test_parse_fractal_hybrid() {
+        let input = r#"
+        fractal:
+          type: Hybrid
+          max_iterations: 200
+          formulas:
+            - BurningShip
+            - Mandelbrot
+            - type: Mandelbrot
+              power: 3
+            - type: Transcendental
+              function: sin
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let hybrid = fractal.as_any().downcast_ref::<Hybrid>().unwrap();
+        assert_eq!(hybrid.max_iterations, 200);
+        assert_eq!(hybrid.plane, Plane::Parameter);
+        assert_eq!(hybrid.bailout, Bailout::Circle(2.0));
+        assert_eq!(hybrid.steps.len(), 4);
+
+        // Spot-check the order and parameters by applying each step to the same point.
+        let z = Complex::new(0.3, -0.4);
+        let c = Complex::new(0.1, 0.2);
+        assert_eq!(
+            Complex::new(0.3 * 0.3 - 0.4 * 0.4, -2.0 * 0.3 * 0.4) + c,
+            hybrid.steps[0].apply(z, c)
+        );
+        assert_eq!(z * z + c, hybrid.steps[1].apply(z, c));
+        assert_eq!(z.powi(3) + c, hybrid.steps[2].apply(z, c));
+        assert_eq!(z.sin() + c, hybrid.steps[3].apply(z, c));
+    }
+
+    #[test]
+    fn test_parse_fractal_hybrid_errors() {
+        let missing = YamlLoader::load_from_str("{type: Hybrid}").unwrap();
+        assert!(matches!(
+            parse_fractal(&missing[0]),
+            Err(ParsingError::MissingField(ref msg)) if msg == "formulas"
+        ));
+        let empty = YamlLoader::load_from_str("{type: Hybrid, formulas: []}").unwrap();
+        assert!(matches!(
+            parse_fractal(&empty[0]),
+            Err(ParsingError::MissingField(ref msg)) if msg == "formulas"
+        ));
+        let unknown = YamlLoader::load_from_str("{type: Hybrid, formulas: [Newton]}").unwrap();
+        assert!(matches!(
+            parse_fractal(&unknown[0]),
+            Err(ParsingError::BadFractal(_))
+        ));
+        let untyped = YamlLoader::load_from_str("{type: Hybrid, formulas: [{power: 3}]}").unwrap();
+        assert!(matches!(
+            parse_fractal(&untyped[0]),
+            Err(ParsingError::MissingField(ref msg)) if msg == "formula type"
+        ));
+    }
+
+    #[test]
+    fn test_parse_fractal_plane() {
+        let input = r#"
+        fractal:
+          type: BurningShip
+          plane: dynamical
+          c: -1.76-0.03i
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let burning_ship = fractal.as_any().downcast_ref::<BurningShip>().unwrap();
+        assert_eq!(
+            burning_ship.plane,
+            Plane::Dynamical(Complex::new(-1.76, -0.03))
+        );
+
+        let input = r#"
+        fractal:
+          type: Tricorn
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let tricorn = fractal.as_any().downcast_ref::<Tricorn>().unwrap();
+        assert_eq!(tricorn.plane, Plane::Parameter);
+    }
+
+    #[test]
+    fn test_parse_fractal_plane_overrides_julia_alias() {
+        let input = r#"
+        fractal:
+          type: Julia
+          plane: parameter
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let mandelbrot = fractal.as_any().downcast_ref::<Mandelbrot>().unwrap();
+        assert_eq!(mandelbrot.plane, Plane::Parameter);
+    }
+
+    #[test]
+    fn test_parse_fractal_z0() {
+        let input = r#"
+        fractal:
+          type: Mandelbrot
+          z0: 0.1-0.2i
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let mandelbrot = fractal.as_any().downcast_ref::<Mandelbrot>().unwrap();
+        assert_eq!(
+            mandelbrot.plane,
+            Plane::Slice {
+                z0: Some(Complex::new(0.1, -0.2)),
+                c: Complex::new(0.0, 0.0),
+                angle: 0.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_fractal_slice() {
+        let input = r#"
+        fractal:
+          type: BurningShip
+          plane: slice
+          c: -0.5+0.1i
+          angle: 90
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let burning_ship = fractal.as_any().downcast_ref::<BurningShip>().unwrap();
+        assert_eq!(
+            burning_ship.plane,
+            Plane::Slice {
+                z0: None,
+                c: Complex::new(-0.5, 0.1),
+                angle: std::f64::consts::FRAC_PI_2,
+            }
+        );
+
+        let input = r#"
+        fractal:
+          type: Tricorn
+          plane: slice
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let tricorn = fractal.as_any().downcast_ref::<Tricorn>().unwrap();
+        assert_eq!(
+            tricorn.plane,
+            Plane::Slice {
+                z0: None,
+                c: Complex::new(0.0, 0.0),
+                angle: 0.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_fractal_plane_errors() {
+        let missing_c = YamlLoader::load_from_str("{type: Magnet1, plane: dynamical}").unwrap();
+        assert!(matches!(
+            parse_fractal(&missing_c[0]),
+            Err(ParsingError::BadComplexNumber(_))
+        ));
+        let bad_plane = YamlLoader::load_from_str("{type: Mandelbrot, plane: phase}").unwrap();
+        assert!(matches!(
+            parse_fractal(&bad_plane[0]),
+            Err(ParsingError::BadFractal(_))
+        ));
+        let bad_z0 = YamlLoader::load_from_str("{type: Mandelbrot, z0: nowhere}").unwrap();
+        assert!(matches!(
+            parse_fractal(&bad_z0[0]),
+            Err(ParsingError::BadComplexNumber(_))
+        ));
+        let bad_angle = YamlLoader::load_from_str("{type: Mandelbrot, angle: steep}").unwrap();
+        assert!(matches!(
+            parse_fractal(&bad_angle[0]),
+            Err(ParsingError::BadFloat(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_fractal_plane_rejects_unused_z0_and_angle() {
+        for input in [
+            "{type: Julia, c: 0.3+0.5i, z0: 0.1}",
+            "{type: TricornJulia, c: 0.3+0.5i, angle: 45}",
+            "{type: BurningShip, plane: dynamical, c: 0.1, z0: 0.2+0.2i}",
+        ] {
+            let docs = YamlLoader::load_from_str(input).unwrap();
+            assert_eq!(
+                Err(ParsingError::BadFractal(
+                    "z0 and angle don't apply to the dynamical plane, where z starts at the pixel"
+                        .to_string()
+                )),
+                parse_fractal(&docs[0]).map(|_| ()),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_fractal_plane_rejects_unused_c() {
+        for input in [
+            "{type: Mandelbrot, c: 0.3+0.5i}",
+            "{type: Julia, plane: parameter, c: 0.3+0.5i}",
+            "{type: BurningShip, plane: parameter, c: 0.1}",
+        ] {
+            let docs = YamlLoader::load_from_str(input).unwrap();
+            assert_eq!(
+                Err(ParsingError::BadFractal(
+                    "c only applies to the dynamical plane or a slice, not the parameter plane"
+                        .to_string()
+                )),
+                parse_fractal(&docs[0]).map(|_| ()),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_fractal_bailout_defaults_to_escape_length() {
+        let input = r#"
+        fractal:
+          type: Julia
+          c: -0.4+0.6i
+          escapeLength: 3
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let julia = fractal.as_any().downcast_ref::<Mandelbrot>().unwrap();
+        assert_eq!(julia.bailout, Bailout::Circle(3.0));
+    }
+
+    #[test]
+    fn test_parse_fractal_bailout_section() {
+        let input = r#"
+        fractal:
+          type: Julia
+          c: 0.5+0.0i
+          bailout:
+            type: biomorph
+            radius: 10
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let julia = fractal.as_any().downcast_ref::<Mandelbrot>().unwrap();
+        assert_eq!(julia.bailout, Bailout::Biomorph(10.0));
+    }
+
+    #[test]
+    fn test_parse_fractal_bailout_shape_only() {
+        let input = r#"
+        fractal:
+          type: Magnet1
+          escapeLength: 50
+          bailout: square
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let magnet1 = fractal.as_any().downcast_ref::<Magnet1>().unwrap();
+        assert_eq!(magnet1.bailout, Bailout::Square(50.0));
+    }
+
+    #[test]
+    fn test_parse_fractal_bailout_overrides_function_default() {
+        let input = r#"
+        fractal:
+          type: Transcendental
+          function: sin
+          bailout:
+            type: circle
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let transcendental = fractal.as_any().downcast_ref::<Transcendental>().unwrap();
+        assert_eq!(transcendental.bailout, Bailout::Circle(50.0));
+    }
+
+    #[test]
+    fn test_parse_bailout() {
+        let shapes = [
+            ("circle", Bailout::Circle(4.0)),
+            ("square", Bailout::Square(4.0)),
+            ("l1", Bailout::L1(4.0)),
+            ("real", Bailout::Real(4.0)),
+            ("imaginary", Bailout::Imaginary(4.0)),
+            ("biomorph", Bailout::Biomorph(4.0)),
+        ];
+        for (name, bailout) in shapes.iter() {
+            assert_eq!(
+                Ok(*bailout),
+                parse_bailout(&Yaml::String(name.to_string()), Bailout::Circle, 4.0)
+            );
+        }
+        assert_eq!(
+            Ok(Bailout::Real(4.0)),
+            parse_bailout(&Yaml::BadValue, Bailout::Real, 4.0)
+        );
+    }
+
+    #[test]
+    fn test_parse_bailout_errors() {
+        assert!(matches!(
+            parse_bailout(&Yaml::String("oval".to_string()), Bailout::Circle, 2.0),
+            Err(ParsingError::BadBailout(_))
+        ));
+        assert!(matches!(
+            parse_bailout(&Yaml::Integer(2), Bailout::Circle, 2.0),
+            Err(ParsingError::BadBailout(_))
+        ));
+        let missing_type = YamlLoader::load_from_str("{radius: 4}").unwrap();
+        assert!(matches!(
+            parse_bailout(&missing_type[0], Bailout::Circle, 2.0),
+            Err(ParsingError::MissingField(ref msg)) if msg == "bailout type"
+        ));
+        let bad_radius = YamlLoader::load_from_str("{type: square, radius: big}").unwrap();
+        assert!(matches!(
+            parse_bailout(&bad_radius[0], Bailout::Circle, 2.0),
+            Err(ParsingError::BadFloat(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_fractal_formula() {
+        let input = r#"
+        fractal:
+          type: Formula
+          formula: z^3 + c*sin(z)
+          max_iterations: 64
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let formula = fractal.as_any().downcast_ref::<Formula>().unwrap();
+        assert_eq!(formula.max_iterations, 64);
+        assert_eq!(
+            formula.expression,
+            Expression::parse("z^3 + c*sin(z)").unwrap()
+        );
+        assert_eq!(formula.plane, Plane::Parameter);
+        assert_eq!(formula.bailout, Bailout::Circle(2.0));
+    }
+
+    #[test]
+    fn test_parse_fractal_formula_with_c() {
+        let input = r#"
+        fractal:
+          type: Formula
+          formula: conj(z)^2 + c
+          plane: dynamical
+          c: -0.4+0.6i
+          bailout: square
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let formula = fractal.as_any().downcast_ref::<Formula>().unwrap();
+        assert_eq!(formula.plane, Plane::Dynamical(Complex::new(-0.4, 0.6)));
+        assert_eq!(formula.bailout, Bailout::Square(2.0));
+    }
+
+    #[test]
+    fn test_parse_fractal_formula_errors() {
+        let missing = YamlLoader::load_from_str("{type: Formula}").unwrap();
+        assert!(matches!(
+            parse_fractal(&missing[0]),
+            Err(ParsingError::MissingField(ref msg)) if msg == "formula"
+        ));
+        let bad = YamlLoader::load_from_str("{type: Formula, formula: 'z^2 + tan(z)'}").unwrap();
+        assert!(matches!(
+            parse_fractal(&bad[0]),
+            Err(ParsingError::BadFormula(7, _))
+        ));
+    }
+
+    #[test]
+    fn test_parse_fractal_transcendental() {
+        let input = r#"
+        fractal:
+          type: Transcendental
+          function: exp
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let transcendental = fractal.as_any().downcast_ref::<Transcendental>().unwrap();
+        assert_eq!(transcendental.max_iterations, 128);
+        assert_eq!(transcendental.function, Function::Exp);
+        assert_eq!(transcendental.bailout, Bailout::Real(50.0));
+    }
+
+    #[test]
+    fn test_parse_fractal_transcendental_julia() {
+        let input = r#"
+        fractal:
+          type: TranscendentalJulia
+          function: sin
+          c: 1+0.1i
+          escapeLength: 20
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let julia = fractal.as_any().downcast_ref::<Transcendental>().unwrap();
+        assert_eq!(julia.plane, Plane::Dynamical(Complex::new(1.0, 0.1)));
+        assert_eq!(julia.function, Function::Sin);
+        assert_eq!(julia.bailout, Bailout::Imaginary(20.0));
+    }
+
+    #[test]
+    fn test_parse_fractal_transcendental_errors() {
+        let missing = YamlLoader::load_from_str("{type: Transcendental}").unwrap();
+        assert!(matches!(
+            parse_fractal(&missing[0]),
+            Err(ParsingError::MissingField(ref msg)) if msg == "function"
+        ));
+        let unknown = YamlLoader::load_from_str("{type: Transcendental, function: tan}").unwrap();
+        assert!(matches!(
+            parse_fractal(&unknown[0]),
+            Err(ParsingError::BadFractal(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_fractal_mcmullen() {
+        let input = r#"
+        fractal:
+          type: McMullen
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let mcmullen = fractal.as_any().downcast_ref::<McMullen>().unwrap();
+        assert_eq!(mcmullen.plane, Plane::Parameter);
+        assert_eq!(mcmullen.n, Power::Integer(3));
+        assert_eq!(mcmullen.m, Power::Integer(3));
+        assert_eq!(mcmullen.bailout, Bailout::Circle(10.0));
+
+        let input = r#"
+        fractal:
+          type: McMullenJulia
+          c: -0.01+0.0i
+          n: 2
+          m: 4
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let mcmullen = fractal.as_any().downcast_ref::<McMullen>().unwrap();
+        assert_eq!(mcmullen.plane, Plane::Dynamical(Complex::new(-0.01, 0.0)));
+        assert_eq!(mcmullen.n, Power::Integer(2));
+        assert_eq!(mcmullen.m, Power::Integer(4));
+    }
+
+    #[test]
+    fn test_parse_fractal_mcmullen_bad_powers() {
+        for (powers, expected) in [
+            ("n: 0", "n must not be 0".to_string()),
+            ("n: 0.0, m: 2", "n must not be 0".to_string()),
+            (
+                "n: 2, m: -2",
+                "n + m must not be 0, got n = Integer(2) and m = Integer(-2)".to_string(),
+            ),
+            (
+                "n: 1+1i, m: -1-1i",
+                "n + m must not be 0, got n = Complex(Complex { re: 1.0, im: 1.0 }) and m = Complex(Complex { re: -1.0, im: -1.0 })".to_string(),
+            ),
+        ] {
+            let input = format!("fractal: {{type: McMullen, {}}}", powers);
+            let docs = YamlLoader::load_from_str(&input).unwrap();
+            assert_eq!(
+                Err(ParsingError::BadFractal(expected)),
+                parse_fractal(&docs[0]["fractal"]).map(|_| ())
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_fractal_nova() {
+        let input = r#"
+        fractal:
+          type: Nova
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let nova = fractal.as_any().downcast_ref::<Nova>().unwrap();
+        assert_eq!(nova.max_iterations, 128);
+        assert_eq!(nova.relaxation, Complex::new(1.0, 0.0));
+        assert_eq!(nova.power, Power::Integer(3));
+        assert_eq!(nova.epsilon, 1e-6);
+        assert_eq!(nova.bailout, Bailout::Circle(1.0e3));
+    }
+
+    #[test]
+    fn test_parse_fractal_nova_julia() {
+        let input = r#"
+        fractal:
+          type: NovaJulia
+          c: -0.5+0.1i
+          relaxation: 0.5+0.25i
+          power: 5
+          epsilon: 0.0001
+          escapeLength: 20
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+
+        let nova_julia = fractal.as_any().downcast_ref::<Nova>().unwrap();
+        assert_eq!(nova_julia.plane, Plane::Dynamical(Complex::new(-0.5, 0.1)));
+        assert_eq!(nova_julia.relaxation, Complex::new(0.5, 0.25));
+        assert_eq!(nova_julia.power, Power::Integer(5));
+        assert_eq!(nova_julia.epsilon, 0.0001);
+        assert_eq!(nova_julia.bailout, Bailout::Circle(20.0));
+    }
+
+    #[test]
+    fn test_parse_fractal_nova_julia_missing_c() {
+        let input = r#"
+        fractal:
+          type: NovaJulia
+          relaxation: 1
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let result = parse_fractal(&docs[0]["fractal"]);
+        assert!(matches!(result, Err(ParsingError::BadComplexNumber(_))));
+    }
+
+    #[test]
+    fn test_parse_fractal_nova_bad_power() {
+        for power in ["0", "1", "0.0", "1.0", "0+0i", "1+0i", "1-0i"] {
+            let input = format!("fractal: {{type: Nova, power: {}}}", power);
+            let docs = YamlLoader::load_from_str(&input).unwrap();
+            let result = parse_fractal(&docs[0]["fractal"]);
+            assert!(
+                matches!(result, Err(ParsingError::BadFractal(_))),
+                "power {}",
+                power
+            );
+        }
+
+        for (power, expected) in [
+            ("-2", Power::Integer(-2)),
+            ("0.5", Power::Real(0.5)),
+            ("-1.5", Power::Real(-1.5)),
+            ("1.5", Power::Real(1.5)),
+            ("1+0.5i", Power::Complex(Complex::new(1.0, 0.5))),
+            ("0.5+0.5i", Power::Complex(Complex::new(0.5, 0.5))),
+        ] {
+            let input = format!("fractal: {{type: Nova, power: {}}}", power);
+            let docs = YamlLoader::load_from_str(&input).unwrap();
+            let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+            let nova = fractal.as_any().downcast_ref::<Nova>().unwrap();
+            assert_eq!(nova.power, expected, "power {}", power);
+        }
+    }
+
+    #[test]
+    fn test_parse_color_scheme_basins() {
+        let input = r#"
+        color_scheme:
+          type: Basins
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let cs = parse_color_scheme(&docs[0]["color_scheme"]).unwrap();
+        assert_eq!(
+            Color::new(1.0, 0.25, 0.25),
+            cs.color(Iteration::Converged {
+                root_index: 0,
+                iterations: 0,
+                max_iterations: 64
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_complex_invalid_format() {
+        let result = parse_complex(&Yaml::String("invalid".to_string()));
+        assert_eq!(
+            result,
+            Err(ParsingError::BadComplexNumber("invalid".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_complex_not_a_string() {
+        let result = parse_complex(&Yaml::Integer(42));
+        assert!(matches!(result, Err(ParsingError::BadComplexNumber(_))));
+    }
+
+    #[test]
+    fn test_parse_size_invalid_format() {
+        let result = parse_size(&Yaml::String("100".to_string()));
+        assert!(matches!(result, Err(ParsingError::BadSize(_))));
+    }
+
+    #[test]
+    fn test_parse_size_invalid_numbers() {
+        let result = parse_size(&Yaml::String("100xabc".to_string()));
+        assert!(matches!(result, Err(ParsingError::BadSize(_))));
+    }
+
+    #[test]
+    fn test_parse_size_too_many_dimensions() {
+        let result = parse_size(&Yaml::String("100x200x300".to_string()));
+        assert!(matches!(result, Err(ParsingError::BadSize(_))));
+    }
+
+    #[test]
+    fn test_parse_color_scheme_missing_type() {
+        let input = r#"
+        color_scheme:
+          foo: bar
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let result = parse_color_scheme(&docs[0]["color_scheme"]);
+        assert!(matches!(
+            result,
+            Err(ParsingError::MissingField(ref msg)) if msg == "color_scheme type"
+        ));
+    }
+
+    #[test]
+    fn test_parse_color_scheme_invalid_type() {
+        let input = r#"
+        color_scheme:
+          type: InvalidScheme
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let result = parse_color_scheme(&docs[0]["color_scheme"]);
+        assert!(matches!(result, Err(ParsingError::BadColorScheme(_))));
+    }
+
+    #[test]
+    fn test_parse_image_missing_upper_left() {
+        let input = r#"
+      image:
+        size: 512x384
+        lowerRight: 1.2+-1.2i
+    "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let result = parse_image(&String::from("data/foobar.yml"), &docs[0]["image"]);
+        assert!(matches!(result, Err(ParsingError::BadComplexNumber(_))));
+    }
+
+    #[test]
+    fn test_parse_image_missing_lower_right() {
+        let input = r#"
+      image:
+        size: 512x384
+        upperLeft: -2.0+1.2i
+    "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let result = parse_image(&String::from("data/foobar.yml"), &docs[0]["image"]);
+        assert!(matches!(result, Err(ParsingError::BadComplexNumber(_))));
+    }
+
+    #[test]
+    fn test_parse_u64_string_value() {
+        let result = parse_u64(&Yaml::String("not a number".to_string()));
+        assert_eq!(
+            result,
+            Err(ParsingError::BadInteger("not a number".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_nonexistent_file() {
+        let result = parse(&String::from("/nonexistent/file.yml"));
+        assert!(matches!(result, Err(ParsingError::IoError(_))));
+    }
+
+    #[test]
+    fn test_parse_power() {
+        let parse = |input: &str| {
+            let docs = YamlLoader::load_from_str(input).unwrap();
+            parse_power(&docs[0]["power"])
+        };
+        assert_eq!(Ok(Power::Integer(2)), parse("foo: 1"));
+        assert_eq!(Ok(Power::Integer(3)), parse("power: 3"));
+        assert_eq!(Ok(Power::Integer(-2)), parse("power: -2"));
+        assert_eq!(Ok(Power::Integer(4)), parse("power: 4.0"));
+        assert_eq!(Ok(Power::Real(2.5)), parse("power: 2.5"));
+        assert_eq!(
+            Ok(Power::Complex(Complex::new(2.0, 0.25))),
+            parse("power: 2+0.25i")
+        );
+    }
+
+    #[test]
+    fn test_parse_power_errors() {
+        let parse = |input: &str| {
+            let docs = YamlLoader::load_from_str(input).unwrap();
+            parse_power(&docs[0]["power"])
+        };
+        assert!(matches!(
+            parse("power: 5000000000"),
+            Err(ParsingError::BadInteger(_))
+        ));
+        assert!(matches!(
+            parse("power: cubic"),
+            Err(ParsingError::BadComplexNumber(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_fractal_power() {
+        let input = r#"
+        fractal:
+          type: Mandelbrot
+          power: 3
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+        let mandelbrot = fractal.as_any().downcast_ref::<Mandelbrot>().unwrap();
+        assert_eq!(mandelbrot.power, Power::Integer(3));
+
+        let input = r#"
+        fractal:
+          type: Julia
+          c: 0.0+1.0i
+          power: 1.5+0.5i
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+        let julia = fractal.as_any().downcast_ref::<Mandelbrot>().unwrap();
+        assert_eq!(julia.power, Power::Complex(Complex::new(1.5, 0.5)));
+
+        let input = r#"
+        fractal:
+          type: BurningShip
+      "#;
+        let docs = YamlLoader::load_from_str(input).unwrap();
+        let fractal = parse_fractal(&docs[0]["fractal"]).unwrap();
+        let burning_ship = fractal.as_any().downcast_ref::<BurningShip>().unwrap();
+        assert_eq!(burning_ship.power, Power::Integer(2));
     }
 
     #[test]
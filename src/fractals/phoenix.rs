@@ -0,0 +1,154 @@
+use super::bailout::Bailout;
+use super::escape_time::{escape_time_with_previous, EscapeTime, Iteration};
+use super::plane::Plane;
+use num_complex::Complex;
+
+#[cfg(test)]
+use std::any::Any;
+
+/// Iterates `z² + c + p·z_prev`.
+#[derive(Debug)]
+pub struct Phoenix {
+    pub max_iterations: i64,
+    pub plane: Plane,
+    pub p: Complex<f64>,
+    pub bailout: Bailout,
+}
+
+impl EscapeTime for Phoenix {
+    fn iterate(&self, pixel: &Complex<f64>) -> Iteration {
+        let (z0, c) = self.plane.start(*pixel, Complex::new(0.0, 0.0));
+        phoenix(z0, c, self.p, &self.bailout, self.max_iterations)
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+fn phoenix(
+    z0: Complex<f64>,
+    c: Complex<f64>,
+    p: Complex<f64>,
+    bailout: &Bailout,
+    max_iterations: i64,
+) -> Iteration {
+    escape_time_with_previous(z0, bailout, max_iterations, 2.0, |z, z_prev| {
+        z * z + c + p * z_prev
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phoenix(p: Complex<f64>) -> Phoenix {
+        Phoenix {
+            max_iterations: 512,
+            plane: Plane::Parameter,
+            p,
+            bailout: Bailout::Circle(2.0),
+        }
+    }
+
+    fn phoenix_julia() -> Phoenix {
+        Phoenix {
+            max_iterations: 512,
+            plane: Plane::Dynamical(Complex::new(0.5667, 0.0)),
+            p: Complex::new(-0.5, 0.0),
+            bailout: Bailout::Circle(2.0),
+        }
+    }
+
+    #[test]
+    fn test_iterate_inside() {
+        let m = phoenix(Complex::new(-0.5, 0.0));
+
+        assert_eq!(
+            Iteration::Inside {
+                iterations: 512,
+                max_iterations: 512
+            },
+            m.iterate(&Complex::new(0.4, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_iterate_outside() {
+        let m = phoenix(Complex::new(-0.5, 0.0));
+
+        assert_eq!(
+            Iteration::Outside {
+                iterations: 1,
+                max_iterations: 512,
+                final_z: Complex::new(2.0, 2.0),
+                escape_length: 2.0,
+                degree: 2.0,
+            },
+            m.iterate(&Complex::new(2.0, 2.0))
+        );
+        assert!(matches!(
+            m.iterate(&Complex::new(-1.5, 0.0)),
+            Iteration::Outside { iterations: 6, .. }
+        ));
+    }
+
+    #[test]
+    fn test_uses_previous_iterate() {
+        // z1 = c = 1, z2 = 1 + 1 + 0 = 2, but z3 = 4 + 1 + p·z1 gets the previous iterate.
+        let m = phoenix(Complex::new(0.5, 0.0));
+        let m = Phoenix {
+            bailout: Bailout::Circle(5.0),
+            ..m
+        };
+
+        assert_eq!(
+            Iteration::Outside {
+                iterations: 3,
+                max_iterations: 512,
+                final_z: Complex::new(5.5, 0.0),
+                escape_length: 5.0,
+                degree: 2.0,
+            },
+            m.iterate(&Complex::new(1.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_zero_p_is_mandelbrot() {
+        let m = phoenix(Complex::new(0.0, 0.0));
+
+        assert!(matches!(
+            m.iterate(&Complex::new(0.2, 0.6)),
+            Iteration::Outside { iterations: 12, .. }
+        ));
+        assert!(matches!(
+            m.iterate(&Complex::new(-1.5, 0.0)),
+            Iteration::Inside { .. }
+        ));
+    }
+
+    #[test]
+    fn test_julia_iterate_inside() {
+        assert_eq!(
+            Iteration::Inside {
+                iterations: 512,
+                max_iterations: 512
+            },
+            phoenix_julia().iterate(&Complex::new(0.0, 0.7))
+        );
+    }
+
+    #[test]
+    fn test_julia_iterate_outside() {
+        assert!(matches!(
+            phoenix_julia().iterate(&Complex::new(0.0, 0.0)),
+            Iteration::Outside { iterations: 6, .. }
+        ));
+        assert!(matches!(
+            phoenix_julia().iterate(&Complex::new(0.6, 0.6)),
+            Iteration::Outside { iterations: 15, .. }
+        ));
+    }
+}
@@ -0,0 +1,142 @@
+use num_complex::Complex;
+
+/// Which of the two starting values of an iteration comes from the pixel.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Plane {
+    /// c from the pixel and z0 fixed, as for the Mandelbrot set.
+    Parameter,
+    /// z0 from the pixel and c fixed, as for a Julia set.
+    Dynamical(Complex<f64>),
+    /// A plane through the four-dimensional (z0, c) space, as in Julibrot slices:
+    /// `z0 + sin(angle)·pixel` and `c + cos(angle)·pixel`.  An angle of 0 is the parameter plane
+    /// seeded from z0 (the critical point if `None`) and a right angle is the dynamical plane.
+    Slice {
+        z0: Option<Complex<f64>>,
+        c: Complex<f64>,
+        angle: f64,
+    },
+}
+
+impl Plane {
+    /// The `(z0, c)` to iterate from at pixel, where `critical_point` is the z0 of the parameter
+    /// plane unless a slice says otherwise.
+    pub fn start(
+        &self,
+        pixel: Complex<f64>,
+        critical_point: Complex<f64>,
+    ) -> (Complex<f64>, Complex<f64>) {
+        self.start_with(pixel, |_| critical_point)
+    }
+
+    /// Like `start`, for families whose critical point moves with c.
+    pub fn start_with<F>(
+        &self,
+        pixel: Complex<f64>,
+        critical_point: F,
+    ) -> (Complex<f64>, Complex<f64>)
+    where
+        F: FnOnce(Complex<f64>) -> Complex<f64>,
+    {
+        match self {
+            Plane::Parameter => (critical_point(pixel), pixel),
+            Plane::Dynamical(c) => (pixel, *c),
+            Plane::Slice { z0, c, angle } => {
+                let c = c + angle.cos() * pixel;
+                (
+                    z0.unwrap_or_else(|| critical_point(c)) + angle.sin() * pixel,
+                    c,
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parameter() {
+        assert_eq!(
+            (Complex::new(1.0, 0.0), Complex::new(0.5, 0.25)),
+            Plane::Parameter.start(Complex::new(0.5, 0.25), Complex::new(1.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_dynamical() {
+        assert_eq!(
+            (Complex::new(0.5, 0.25), Complex::new(-0.4, 0.6)),
+            Plane::Dynamical(Complex::new(-0.4, 0.6))
+                .start(Complex::new(0.5, 0.25), Complex::new(1.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_start_with_moving_critical_point() {
+        let half = |c: Complex<f64>| c / 2.0;
+        assert_eq!(
+            (Complex::new(0.25, 0.5), Complex::new(0.5, 1.0)),
+            Plane::Parameter.start_with(Complex::new(0.5, 1.0), half)
+        );
+        let slice = Plane::Slice {
+            z0: None,
+            c: Complex::new(1.0, 0.0),
+            angle: 0.0,
+        };
+        assert_eq!(
+            (Complex::new(1.0, 0.0), Complex::new(2.0, 0.0)),
+            slice.start_with(Complex::new(1.0, 0.0), half)
+        );
+    }
+
+    #[test]
+    fn test_slice_at_zero_is_seeded_parameter_plane() {
+        let slice = Plane::Slice {
+            z0: Some(Complex::new(0.1, -0.2)),
+            c: Complex::new(0.0, 0.0),
+            angle: 0.0,
+        };
+        assert_eq!(
+            (Complex::new(0.1, -0.2), Complex::new(0.5, 0.25)),
+            slice.start(Complex::new(0.5, 0.25), Complex::new(1.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_slice_defaults_to_critical_point() {
+        let slice = Plane::Slice {
+            z0: None,
+            c: Complex::new(0.0, 0.0),
+            angle: 0.0,
+        };
+        assert_eq!(
+            (Complex::new(1.0, 0.0), Complex::new(0.5, 0.25)),
+            slice.start(Complex::new(0.5, 0.25), Complex::new(1.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_slice_at_right_angle_is_dynamical_plane() {
+        let slice = Plane::Slice {
+            z0: Some(Complex::new(0.0, 0.0)),
+            c: Complex::new(-0.4, 0.6),
+            angle: std::f64::consts::FRAC_PI_2,
+        };
+        let (z0, c) = slice.start(Complex::new(0.5, 0.25), Complex::new(1.0, 0.0));
+        assert!((z0 - Complex::new(0.5, 0.25)).norm() < 1e-15);
+        assert!((c - Complex::new(-0.4, 0.6)).norm() < 1e-15);
+    }
+
+    #[test]
+    fn test_slice_in_between() {
+        let slice = Plane::Slice {
+            z0: Some(Complex::new(0.0, 0.0)),
+            c: Complex::new(0.0, 0.0),
+            angle: std::f64::consts::FRAC_PI_6,
+        };
+        let (z0, c) = slice.start(Complex::new(2.0, 0.0), Complex::new(1.0, 0.0));
+        assert!((z0 - Complex::new(1.0, 0.0)).norm() < 1e-15);
+        assert!((c - Complex::new(3.0_f64.sqrt(), 0.0)).norm() < 1e-15);
+    }
+}
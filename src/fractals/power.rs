@@ -0,0 +1,120 @@
+use num_complex::Complex;
+
+/// The exponent `n` in `z^n + c`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Power {
+    /// Computed with repeated multiplication; 2 is exactly `z * z`.
+    Integer(i32),
+    Real(f64),
+    Complex(Complex<f64>),
+}
+
+impl Power {
+    pub fn apply(&self, z: Complex<f64>) -> Complex<f64> {
+        match self {
+            Power::Integer(2) => z * z,
+            Power::Integer(n) => z.powi(*n),
+            // Both go through log(z), which is undefined at the origin.
+            Power::Real(_) | Power::Complex(_) if z.re == 0.0 && z.im == 0.0 => z,
+            Power::Real(p) => z.powf(*p),
+            Power::Complex(p) => z.powc(*p),
+        }
+    }
+
+    pub fn exponent(&self) -> Complex<f64> {
+        match self {
+            Power::Integer(n) => Complex::new(*n as f64, 0.0),
+            Power::Real(p) => Complex::new(*p, 0.0),
+            Power::Complex(p) => *p,
+        }
+    }
+
+    /// How fast `|z^n|` grows with `|z|`: the real part of the exponent.
+    pub fn degree(&self) -> f64 {
+        self.exponent().re
+    }
+
+    /// The power one lower, as in the derivative `n z^(n-1)`, or `None` if that is not an `i32`.
+    pub fn lowered(&self) -> Option<Power> {
+        match self {
+            Power::Integer(n) => n.checked_sub(1).map(Power::Integer),
+            Power::Real(p) => Some(Power::Real(p - 1.0)),
+            Power::Complex(p) => Some(Power::Complex(p - 1.0)),
+        }
+    }
+}
+
+impl Default for Power {
+    fn default() -> Self {
+        Power::Integer(2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_eq_float::*;
+
+    #[test]
+    fn test_square_is_exact() {
+        let z = Complex::new(0.1234567, -1.7654321);
+        assert_eq!(z * z, Power::Integer(2).apply(z));
+        assert_eq!(z * z, Power::default().apply(z));
+    }
+
+    #[test]
+    fn test_integer() {
+        let z = Complex::new(1.0, 1.0);
+        assert_eq!(Complex::new(-2.0, 2.0), Power::Integer(3).apply(z));
+        assert_eq!(Complex::new(-4.0, 0.0), Power::Integer(4).apply(z));
+        assert_eq!(Complex::new(0.5, -0.5), Power::Integer(-1).apply(z));
+    }
+
+    #[test]
+    fn test_real() {
+        let result = Power::Real(2.5).apply(Complex::new(4.0, 0.0));
+        assert_eq_float!(32.0, result.re);
+        assert_eq_float!(0.0, result.im);
+
+        let result = Power::Real(0.5).apply(Complex::new(0.0, 2.0));
+        assert_eq_float!(1.0, result.re);
+        assert_eq_float!(1.0, result.im);
+    }
+
+    #[test]
+    fn test_complex() {
+        // i^i = e^(-pi/2)
+        let result = Power::Complex(Complex::new(0.0, 1.0)).apply(Complex::new(0.0, 1.0));
+        assert_eq_float!((-std::f64::consts::FRAC_PI_2).exp(), result.re);
+        assert_eq_float!(0.0, result.im);
+    }
+
+    #[test]
+    fn test_exponent() {
+        assert_eq!(Complex::new(3.0, 0.0), Power::Integer(3).exponent());
+        assert_eq!(Complex::new(2.5, 0.0), Power::Real(2.5).exponent());
+        assert_eq!(
+            Complex::new(2.0, 1.0),
+            Power::Complex(Complex::new(2.0, 1.0)).exponent()
+        );
+    }
+
+    #[test]
+    fn test_lowered() {
+        assert_eq!(Some(Power::Integer(2)), Power::Integer(3).lowered());
+        assert_eq!(Some(Power::Real(1.5)), Power::Real(2.5).lowered());
+        assert_eq!(
+            Some(Power::Complex(Complex::new(1.0, 1.0))),
+            Power::Complex(Complex::new(2.0, 1.0)).lowered()
+        );
+        assert_eq!(None, Power::Integer(i32::MIN).lowered());
+    }
+
+    #[test]
+    fn test_origin() {
+        let origin = Complex::new(0.0, 0.0);
+        assert_eq!(origin, Power::Integer(5).apply(origin));
+        assert_eq!(origin, Power::Real(2.5).apply(origin));
+        assert_eq!(origin, Power::Complex(Complex::new(2.0, 0.5)).apply(origin));
+    }
+}
@@ -0,0 +1,197 @@
+use super::bailout::Bailout;
+use super::escape_time::{escape_time, EscapeTime, Iteration, Step};
+use super::plane::Plane;
+use num_complex::Complex;
+use std::f64::consts::FRAC_PI_2;
+
+#[cfg(test)]
+use std::any::Any;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Function {
+    Sin,
+    Cos,
+    Exp,
+    Sinh,
+    Cosh,
+}
+
+impl Function {
+    pub fn apply(&self, z: Complex<f64>) -> Complex<f64> {
+        match self {
+            Function::Sin => z.sin(),
+            Function::Cos => z.cos(),
+            Function::Exp => z.exp(),
+            Function::Sinh => z.sinh(),
+            Function::Cosh => z.cosh(),
+        }
+    }
+
+    /// Where the derivative vanishes, so the orbit of this point decides connectedness the way
+    /// the orbit of 0 does for `z² + c`.  `exp` has no critical point and starts from 0.
+    pub fn critical_point(&self) -> Complex<f64> {
+        match self {
+            Function::Sin => Complex::new(FRAC_PI_2, 0.0),
+            Function::Sinh => Complex::new(0.0, FRAC_PI_2),
+            Function::Cos | Function::Exp | Function::Cosh => Complex::new(0.0, 0.0),
+        }
+    }
+
+    /// `sin` and `cos` grow exponentially with `Im z` but stay bounded along the real axis, and
+    /// the others the other way round, so a circle would catch far too many points.
+    pub fn bailout(&self, limit: f64) -> Bailout {
+        match self {
+            Function::Sin | Function::Cos => Bailout::Imaginary(limit),
+            Function::Exp | Function::Sinh | Function::Cosh => Bailout::Real(limit),
+        }
+    }
+}
+
+/// Iterates `f(z) + c`.  The parameter plane starts from the critical point of `f`.
+#[derive(Debug)]
+pub struct Transcendental {
+    pub max_iterations: i64,
+    pub plane: Plane,
+    pub function: Function,
+    pub bailout: Bailout,
+}
+
+impl EscapeTime for Transcendental {
+    fn iterate(&self, pixel: &Complex<f64>) -> Iteration {
+        let (z0, c) = self.plane.start(*pixel, self.function.critical_point());
+        escape_time(
+            z0,
+            c,
+            &self.bailout,
+            self.max_iterations,
+            &[TranscendentalStep {
+                function: self.function,
+            }],
+        )
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// One iteration of `f(z) + c`.
+#[derive(Debug)]
+pub struct TranscendentalStep {
+    pub function: Function,
+}
+
+impl Step for TranscendentalStep {
+    fn apply(&self, z: Complex<f64>, c: Complex<f64>) -> Complex<f64> {
+        self.function.apply(z) + c
+    }
+
+    /// The functions grow faster than any power.
+    fn degree(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcendental(function: Function) -> Transcendental {
+        Transcendental {
+            max_iterations: 256,
+            plane: Plane::Parameter,
+            function,
+            bailout: function.bailout(50.0),
+        }
+    }
+
+    #[test]
+    fn test_iterate_inside() {
+        // With c = 0, sin and sinh pull their critical orbits into the fixed point at 0, and cos
+        // settles on the fixed point of cos near 0.739.
+        for function in &[Function::Sin, Function::Cos, Function::Sinh] {
+            assert_eq!(
+                Iteration::Inside {
+                    iterations: 256,
+                    max_iterations: 256
+                },
+                transcendental(*function).iterate(&Complex::new(0.0, 0.0)),
+                "{:?}",
+                function
+            );
+        }
+        // cosh(0) - 1 = 0 is a fixed point.
+        assert!(matches!(
+            transcendental(Function::Cosh).iterate(&Complex::new(-1.0, 0.0)),
+            Iteration::Inside { .. }
+        ));
+        // exp(z) - 2 has an attracting fixed point near -1.84.
+        assert!(matches!(
+            transcendental(Function::Exp).iterate(&Complex::new(-2.0, 0.0)),
+            Iteration::Inside { .. }
+        ));
+    }
+
+    #[test]
+    fn test_sin_bails_on_imaginary_part() {
+        // sin(π/2) + 60i = 1 + 60i
+        assert_eq!(
+            Iteration::Outside {
+                iterations: 1,
+                max_iterations: 256,
+                final_z: Complex::new(1.0, 60.0),
+                escape_length: 50.0,
+                degree: f64::INFINITY,
+            },
+            transcendental(Function::Sin).iterate(&Complex::new(0.0, 60.0))
+        );
+        // A large real part alone is not an escape for sin.
+        assert!(matches!(
+            transcendental(Function::Sin).iterate(&Complex::new(60.0, 0.0)),
+            Iteration::Inside { .. }
+        ));
+    }
+
+    #[test]
+    fn test_exp_bails_on_real_part() {
+        // exp(0) + 60 = 61
+        assert!(matches!(
+            transcendental(Function::Exp).iterate(&Complex::new(60.0, 0.0)),
+            Iteration::Outside { iterations: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_julia() {
+        let julia = Transcendental {
+            max_iterations: 256,
+            plane: Plane::Dynamical(Complex::new(0.0, 0.0)),
+            function: Function::Sin,
+            bailout: Function::Sin.bailout(50.0),
+        };
+        assert!(matches!(
+            julia.iterate(&Complex::new(0.0, 0.0)),
+            Iteration::Inside { .. }
+        ));
+        assert!(matches!(
+            julia.iterate(&Complex::new(0.0, 60.0)),
+            Iteration::Outside { iterations: 0, .. }
+        ));
+        // sin(5i) = 74.2i
+        assert!(matches!(
+            julia.iterate(&Complex::new(0.0, 5.0)),
+            Iteration::Outside { iterations: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_critical_points() {
+        for function in &[Function::Sin, Function::Cos, Function::Sinh, Function::Cosh] {
+            let z = function.critical_point();
+            let h = 1e-6;
+            let derivative = (function.apply(z + h) - function.apply(z - h)) / (2.0 * h);
+            assert!(derivative.norm() < 1e-9, "{:?}", function);
+        }
+    }
+}
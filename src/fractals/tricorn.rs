@@ -0,0 +1,164 @@
+use super::bailout::Bailout;
+use super::escape_time::{escape_time, EscapeTime, Iteration, Step};
+use super::plane::Plane;
+use super::power::Power;
+use num_complex::Complex;
+
+#[cfg(test)]
+use std::any::Any;
+
+/// The Mandelbar set: iterates `conj(z)² + c`.
+#[derive(Debug)]
+pub struct Tricorn {
+    pub max_iterations: i64,
+    pub plane: Plane,
+    pub bailout: Bailout,
+    pub power: Power,
+}
+
+impl EscapeTime for Tricorn {
+    fn iterate(&self, pixel: &Complex<f64>) -> Iteration {
+        let (z0, c) = self.plane.start(*pixel, Complex::new(0.0, 0.0));
+        escape_time(
+            z0,
+            c,
+            &self.bailout,
+            self.max_iterations,
+            &[TricornStep { power: self.power }],
+        )
+    }
+
+    #[cfg(test)]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// One iteration of `conj(z)^n + c`.
+#[derive(Debug)]
+pub struct TricornStep {
+    pub power: Power,
+}
+
+impl Step for TricornStep {
+    fn apply(&self, z: Complex<f64>, c: Complex<f64>) -> Complex<f64> {
+        self.power.apply(z.conj()) + c
+    }
+
+    fn degree(&self) -> f64 {
+        self.power.degree()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tricorn() -> Tricorn {
+        Tricorn {
+            max_iterations: 512,
+            plane: Plane::Parameter,
+            bailout: Bailout::Circle(2.0),
+            power: Power::default(),
+        }
+    }
+
+    fn tricorn_julia() -> Tricorn {
+        Tricorn {
+            max_iterations: 512,
+            plane: Plane::Dynamical(Complex::new(0.3, 0.52)),
+            bailout: Bailout::Circle(2.0),
+            power: Power::default(),
+        }
+    }
+
+    #[test]
+    fn test_iterate_inside() {
+        assert_eq!(
+            Iteration::Inside {
+                iterations: 512,
+                max_iterations: 512
+            },
+            tricorn().iterate(&Complex::new(0.0, 0.0))
+        );
+        assert_eq!(
+            Iteration::Inside {
+                iterations: 512,
+                max_iterations: 512
+            },
+            tricorn().iterate(&Complex::new(-0.6, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_iterate_outside() {
+        assert_eq!(
+            Iteration::Outside {
+                iterations: 1,
+                max_iterations: 512,
+                final_z: Complex::new(2.0, 2.0),
+                escape_length: 2.0,
+                degree: 2.0,
+            },
+            tricorn().iterate(&Complex::new(2.0, 2.0))
+        );
+    }
+
+    #[test]
+    fn test_conjugates_before_squaring() {
+        // 0.6i is deep inside the Mandelbrot set, but the conjugate sends its orbit
+        // 0.6i -> -0.36+0.6i -> ... out of the Tricorn in five steps.
+        assert!(matches!(
+            tricorn().iterate(&Complex::new(0.0, 0.6)),
+            Iteration::Outside { iterations: 5, .. }
+        ));
+    }
+
+    #[test]
+    fn test_orientation() {
+        // The Tricorn has three-fold symmetry with one arm along the negative real axis, so
+        // the other two arms point up and down to the right.  A formula that negates the
+        // square (-conj(z)² + c) would mirror the whole set left to right.
+        assert!(matches!(
+            tricorn().iterate(&Complex::new(0.3, 0.52)),
+            Iteration::Inside { .. }
+        ));
+        assert!(matches!(
+            tricorn().iterate(&Complex::new(0.3, -0.52)),
+            Iteration::Inside { .. }
+        ));
+        assert!(matches!(
+            tricorn().iterate(&Complex::new(-0.3, 0.52)),
+            Iteration::Outside { iterations: 4, .. }
+        ));
+    }
+
+    #[test]
+    fn test_julia_iterate_inside() {
+        assert_eq!(
+            Iteration::Inside {
+                iterations: 512,
+                max_iterations: 512
+            },
+            tricorn_julia().iterate(&Complex::new(0.5, 0.5))
+        );
+    }
+
+    #[test]
+    fn test_julia_iterate_outside() {
+        assert_eq!(
+            Iteration::Outside {
+                iterations: 0,
+                max_iterations: 512,
+                final_z: Complex::new(2.0, 2.0),
+                escape_length: 2.0,
+                degree: 2.0,
+            },
+            tricorn_julia().iterate(&Complex::new(2.0, 2.0))
+        );
+        assert!(matches!(
+            tricorn_julia().iterate(&Complex::new(0.8, 0.0)),
+            Iteration::Outside { iterations: 4, .. }
+        ));
+    }
+}
@@ -7,18 +7,38 @@ mod fractals;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
-    
-    if args.len() < 2 {
-        eprintln!("Usage: {} <input_filename>", args[0]);
-        std::process::exit(1);
-    }
-    
-    let input_filename = &args[1];
+
+    let (threads, input_filename) = match args.as_slice() {
+        [_, flag, threads, input_filename, ..] if flag == "--threads" => {
+            (Some(parse_threads(threads)), input_filename)
+        }
+        [_, input_filename, ..] if input_filename != "--threads" => (None, input_filename),
+        _ => {
+            eprintln!("Usage: {} [--threads N] <input_filename>", args[0]);
+            std::process::exit(1);
+        }
+    };
+
     match Job::parse(input_filename) {
-        Ok(job) => job.generate(),
+        Ok(mut job) => {
+            if let Some(threads) = threads {
+                job.threads = threads;
+            }
+            job.generate()
+        }
         Err(e) => {
             eprintln!("Error parsing input file: {:?}", e);
             std::process::exit(1);
         }
     }
 }
+
+fn parse_threads(threads: &str) -> usize {
+    match threads.parse::<usize>() {
+        Ok(threads) if threads > 0 => threads,
+        _ => {
+            eprintln!("--threads must be a positive integer, got {}", threads);
+            std::process::exit(1);
+        }
+    }
+}